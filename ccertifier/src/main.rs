@@ -1,5 +1,5 @@
 use clap::{Args, Parser, Subcommand};
-use hazard_analyzer::hazard_analyzer;
+use hazard_analyzer::{hazard_analyzer, lint_devices, AnalysisOptions, DEFAULT_HELPER_DEPTH};
 use pub_api::pub_apis;
 use std::path::PathBuf;
 
@@ -16,6 +16,9 @@ enum Commands {
     /// Hazard analyzer command.
     HazardAnalyzer(AnalyzerArgs),
 
+    /// Device definitions linter command.
+    LintDevices(LintArgs),
+
     /// Public API command.
     PubApi(ApisArgs),
 }
@@ -37,6 +40,50 @@ struct AnalyzerArgs {
     /// If set, the analysis output will not be printed on the terminal.
     #[arg(long, short = 'q', action = clap::ArgAction::SetTrue)]
     quiet: bool,
+
+    /// Maximum number of nested helper functions followed while resolving a device action.
+    #[clap(long, default_value_t = DEFAULT_HELPER_DEPTH)]
+    helper_depth: usize,
+
+    /// Comma-separated list of active features used to evaluate the cfg attributes of the firmware.
+    #[clap(long, value_delimiter = ',')]
+    features: Option<Vec<String>>,
+
+    /// Active cfg option used to evaluate the cfg attributes of the firmware, e.g. `unix`.
+    #[clap(long)]
+    cfg: Vec<String>,
+
+    /// If set, the firmware is also analyzed with each combination of its features.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    all_feature_combinations: bool,
+
+    /// Path to the TOML certification policy.
+    #[clap(long, short = 'p', value_hint = clap::ValueHint::FilePath)]
+    policy_path: Option<PathBuf>,
+
+    /// If set, the bodies of the action handlers are analyzed, following the functions
+    /// they call up to this number of nested calls.
+    #[clap(long)]
+    handler_body_depth: Option<usize>,
+
+    /// Path to the hazards.rs file of ascot-library declaring the Hazard enum.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    hazards_path: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct LintArgs {
+    /// Path to the ascot devices.
+    #[clap(long, short = 'd', value_hint = clap::ValueHint::DirPath)]
+    devices_path: Option<PathBuf>,
+
+    /// Path to the output manifest.
+    #[clap(long, short = 'm', required = true, value_hint = clap::ValueHint::FilePath)]
+    manifest_path: PathBuf,
+
+    /// If set, the inconsistencies will not be printed on the terminal.
+    #[arg(long, short = 'q', action = clap::ArgAction::SetTrue)]
+    quiet: bool,
 }
 
 #[derive(Args)]
@@ -65,9 +112,22 @@ fn main() {
                 &args.firmware_path,
                 &args.manifest_path,
                 args.quiet,
+                &AnalysisOptions {
+                    helper_depth: args.helper_depth,
+                    features: args.features,
+                    cfg: args.cfg,
+                    all_feature_combinations: args.all_feature_combinations,
+                    policy_path: args.policy_path,
+                    handler_body_depth: args.handler_body_depth,
+                    hazards_path: args.hazards_path,
+                },
             )
             .unwrap();
         }
+        // Device definitions linter command.
+        Commands::LintDevices(args) => {
+            lint_devices(args.devices_path, &args.manifest_path, args.quiet).unwrap()
+        }
         // Public API command.
         Commands::PubApi(args) => {
             pub_apis(args.library_path, args.axum_path, &args.manifest_path).unwrap()
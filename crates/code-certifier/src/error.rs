@@ -17,6 +17,8 @@ pub enum ErrorKind {
     Concurrent,
     /// Json error.
     Json,
+    /// Analysis error.
+    Analysis,
 }
 
 impl ErrorKind {
@@ -28,6 +30,7 @@ impl ErrorKind {
             ErrorKind::Rustdoc => "Rustdoc error",
             ErrorKind::Concurrent => "Concurrent error",
             ErrorKind::Json => "JSON error",
+            ErrorKind::Analysis => "Analysis error",
         }
     }
 }
@@ -1,6 +1,9 @@
 //! This module handles the cloning of `ascot-firmware`.
 
-use std::{env::temp_dir, path::PathBuf};
+use std::{
+    env::temp_dir,
+    path::{Path, PathBuf},
+};
 
 use git2::Repository;
 use toml::Value;
@@ -45,3 +48,13 @@ pub fn ascot_devices(ascot_firmware: PathBuf) -> PathBuf {
         .join("src")
         .join("devices")
 }
+
+/// Returns `ascot-firmware/ascot-library/src/hazards.rs` path,
+/// the file declaring the `Hazard` enum.
+#[inline(always)]
+pub fn ascot_hazards(ascot_firmware: &Path) -> PathBuf {
+    ascot_firmware
+        .join("ascot-library")
+        .join("src")
+        .join("hazards.rs")
+}
@@ -1,26 +1,46 @@
-use std::path::Path;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
-use rust_code_analysis::{read_file, Node, RustCode, Tree};
+use rust_code_analysis::{read_file, Node, Rust, RustCode, Search, Tree};
 
 use code_certifier::error::Result;
 
+use crate::modules::{get_submodules, module_name, normalize_path};
+
+// Root file of the devices module.
+const DEVICES_MODULE: &str = "mod.rs";
+
 // Ascot device file.
 pub(crate) struct DeviceFile {
-    // Device name.
+    // Device name derived from the file name,
+    // used when the file defines no device struct.
     pub(crate) name: String,
+    // File path.
+    pub(crate) path: PathBuf,
     // File source code.
     pub(crate) source_code: Vec<u8>,
     // File AST.
     pub(crate) ast: Tree,
+    // Error raised while reading the file, whose source code is then empty.
+    pub(crate) error: Option<String>,
 }
 
 impl DeviceFile {
-    #[inline(always)]
-    fn new(name: &str, source_code: Vec<u8>, ast: Tree) -> Self {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        let (source_code, error) = match read_file(&path) {
+            Ok(source_code) => (source_code, None),
+            Err(error) => (Vec::new(), Some(error.to_string())),
+        };
+        let ast = Tree::new::<RustCode>(&source_code);
+
         Self {
-            name: to_camel_case(name),
+            name: to_camel_case(module_name(&path).unwrap_or_default()),
+            path,
             source_code,
             ast,
+            error,
         }
     }
 
@@ -30,6 +50,11 @@ impl DeviceFile {
     }
 }
 
+#[inline(always)]
+fn is_rust_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("rs")
+}
+
 // Retrieves the device name by converting
 // file name from snake_case to CamelCase.
 fn to_camel_case(file_name: &str) -> String {
@@ -50,21 +75,76 @@ fn to_camel_case(file_name: &str) -> String {
     result
 }
 
+// Retrieves the files of the modules declared, also recursively,
+// inside the `mod.rs` file of the devices module.
+fn get_module_files(devices_module: &Path) -> Vec<PathBuf> {
+    let mut visited = HashSet::from([devices_module.to_path_buf()]);
+    let mut stack = vec![devices_module.to_path_buf()];
+    let mut files = Vec::new();
+
+    while let Some(path) = stack.pop() {
+        // Keep the files which cannot be read, so that they are reported.
+        let Ok(source_code) = read_file(&path) else {
+            files.push(path);
+            continue;
+        };
+        let ast = Tree::new::<RustCode>(&source_code);
+
+        let submodules = get_submodules(&path, false, ast.get_root(), &source_code);
+        let declares_modules = !submodules.is_empty();
+        for submodule in submodules {
+            if visited.insert(submodule.path.clone()) {
+                stack.push(submodule.path);
+            }
+        }
+
+        // Skip the files which only declare other modules,
+        // such as the `mod.rs` file of the devices module.
+        let defines_structs = ast
+            .get_root()
+            .first_occurence(|n| n.kind_id() == Rust::StructItem)
+            .is_some();
+        if !declares_modules || defines_structs {
+            files.push(path);
+        }
+    }
+
+    files
+}
+
+// Retrieves all Rust files inside the devices directory and its subdirectories.
+fn get_directory_files(devices_path: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![devices_path.to_path_buf()];
+
+    while let Some(current_dir) = stack.pop() {
+        if let Ok(entries) = std::fs::read_dir(current_dir) {
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_dir() {
+                    stack.push(path);
+                } else if is_rust_file(&path) {
+                    files.push(normalize_path(&path));
+                }
+            }
+        }
+    }
+
+    files
+}
+
 // Retrieves list of `DeviceFile` starting from `devices_path`.
+//
+// When the directory contains a `mod.rs` file, the device files are the ones
+// reachable through its `mod` declarations, otherwise all the Rust files
+// inside the directory.
 pub(crate) fn get_device_files(devices_path: &Path) -> Result<Vec<DeviceFile>> {
-    let device_files = std::fs::read_dir(devices_path)?
-        .filter_map(|entry| entry.ok())
-        .filter_map(|entry| {
-            let path = entry.path();
-            let name = path.file_stem()?.to_str()?;
-            if name == "mod" {
-                return None;
-            }
-            let source_code = read_file(&path).ok()?;
-            let ast = Tree::new::<RustCode>(&source_code);
-            Some(DeviceFile::new(name, source_code, ast))
-        })
-        .collect();
+    let devices_module = devices_path.join(DEVICES_MODULE);
+    let mut paths = if devices_module.is_file() {
+        get_module_files(&devices_module)
+    } else {
+        get_directory_files(devices_path)
+    };
+    paths.sort();
 
-    Ok(device_files)
+    Ok(paths.into_iter().map(DeviceFile::new).collect())
 }
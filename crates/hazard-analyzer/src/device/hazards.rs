@@ -0,0 +1,350 @@
+// This module defines the catalog of the hazards
+// declared by the `Hazard` enum of ascot-library,
+// along with their id, description and category.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::Path,
+};
+
+use code_certifier::error::{Error, ErrorKind, Result};
+use rust_code_analysis::{read_file, Node, Rust, RustCode, Search, Tree};
+use serde::Serialize;
+
+use crate::modules::last_segment;
+
+use super::{AscotDevice, MandatoryActions};
+
+// Name of the enum declaring the hazards.
+const HAZARD_ENUM: &str = "Hazard";
+
+// Methods of the `Hazard` enum returning the metadata of each hazard.
+const ID_METHOD: &str = "id";
+const DESCRIPTION_METHOD: &str = "description";
+const CATEGORY_METHOD: &str = "category";
+
+// Metadata of a hazard.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HazardInfo {
+    // Hazard identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) id: Option<u16>,
+    // Hazard description, taken from the variant documentation
+    // when the enum does not define a `description` method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+    // Hazard category, such as `Safety`, `Privacy` or `Financial`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) category: Option<String>,
+}
+
+// Description of a hazard referenced by a manifest.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HazardDescription<'a> {
+    // Hazard name.
+    pub(crate) name: &'a str,
+    // Hazard metadata.
+    #[serde(flatten)]
+    pub(crate) info: &'a HazardInfo,
+}
+
+// Hazard identifier which is not a variant of the `Hazard` enum.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UnknownHazard<'a> {
+    // Hazard name.
+    pub(crate) name: &'a str,
+    // Closest valid hazard name, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) suggestion: Option<&'a str>,
+}
+
+// Unknown hazards used inside the definition of an Ascot device.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UnknownDeviceHazards<'a> {
+    // Device name.
+    pub(crate) device: &'a str,
+    // Unknown hazards, sorted by name.
+    pub(crate) hazards: Vec<UnknownHazard<'a>>,
+}
+
+// Catalog of the valid hazards.
+pub(crate) struct HazardCatalog {
+    // Variants of the `Hazard` enum, along with their metadata.
+    hazards: BTreeMap<String, HazardInfo>,
+}
+
+// Retrieves the text of a doc comment line, without the `///` marker.
+fn doc_text<'a>(node: Node<'a>, source_code: &'a [u8]) -> Option<&'a str> {
+    if node.kind_id() != Rust::LineComment {
+        return None;
+    }
+    node.utf8_text(source_code)?
+        .strip_prefix("///")
+        .map(str::trim)
+}
+
+// Retrieves the variants of the `Hazard` enum, along with their documentation.
+fn hazard_variants<'a>(root: Node<'a>, source_code: &'a [u8]) -> Option<Vec<(&'a str, String)>> {
+    let hazard_enum = root.first_occurence(|n| {
+        n.kind_id() == Rust::EnumItem
+            && n.first_child(|c| c.kind_id() == Rust::TypeIdentifier)
+                .and_then(|name| name.utf8_text(source_code))
+                .map_or(false, |name| name == HAZARD_ENUM)
+    })?;
+    let variants = hazard_enum.first_child(|c| c.kind_id() == Rust::EnumVariantList)?;
+
+    // Doc comments precede the variant they document.
+    let mut hazards = Vec::new();
+    let mut doc = Vec::new();
+    for child in variants.children() {
+        if let Some(text) = doc_text(child, source_code) {
+            doc.push(text);
+        } else if child.kind_id() == Rust::EnumVariant {
+            if let Some(name) = child
+                .first_child(|c| c.kind_id() == Rust::Identifier)
+                .and_then(|name| name.utf8_text(source_code))
+            {
+                hazards.push((name, doc.join(" ")));
+            }
+            doc.clear();
+        }
+    }
+
+    Some(hazards)
+}
+
+// Retrieves the arms of the `match` returning the metadata of each hazard
+// inside the `method` of the `Hazard` enum, as pairs of variant and value.
+//
+// Given for example:
+//
+// pub const fn category(&self) -> Category {
+//     match self {
+//         Self::AirPoisoning | Self::FireHazard => Category::Safety,
+//         ..
+//     }
+// }
+//
+// the value of `AirPoisoning` and `FireHazard` is `Safety`.
+fn method_arms<'a>(
+    root: Node<'a>,
+    method: &str,
+    source_code: &'a [u8],
+) -> HashMap<&'a str, &'a str> {
+    let functions = root
+        .all_occurrences(|n| {
+            n.kind_id() == Rust::ImplItem
+                && n.first_child(|c| c.kind_id() == Rust::TypeIdentifier)
+                    .and_then(|name| name.utf8_text(source_code))
+                    .map_or(false, |name| name == HAZARD_ENUM)
+        })
+        .into_iter()
+        .flat_map(|hazard_impl| {
+            hazard_impl.all_occurrences(|n| {
+                n.kind_id() == Rust::FunctionItem
+                    && n.first_child(|c| c.kind_id() == Rust::Identifier)
+                        .and_then(|name| name.utf8_text(source_code))
+                        .map_or(false, |name| name == method)
+            })
+        });
+
+    let mut arms = HashMap::new();
+    for function in functions {
+        for arm in function.all_occurrences(|n| n.kind_id() == Rust::MatchArm) {
+            let (Some(pattern), Some(value)) = (
+                arm.first_child(|c| c.kind_id() == Rust::MatchPattern)
+                    .and_then(|pattern| pattern.utf8_text(source_code)),
+                arm.child(arm.child_count() - 1)
+                    .filter(|value| value.kind_id() != Rust::COMMA)
+                    .or_else(|| arm.child(arm.child_count() - 2))
+                    .and_then(|value| value.utf8_text(source_code)),
+            ) else {
+                continue;
+            };
+
+            // String values are unquoted, paths are reduced to their last segment.
+            let value = match value.strip_prefix('"') {
+                Some(value) => value.trim_end_matches('"'),
+                None => last_segment(value),
+            };
+            for variant in pattern.split('|') {
+                arms.insert(last_segment(variant), value);
+            }
+        }
+    }
+
+    arms
+}
+
+// Returns the edit distance between two names, ignoring their case.
+fn edit_distance(first: &str, second: &str) -> usize {
+    let first: Vec<_> = first.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let second: Vec<_> = second.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    // Distances between the prefixes of `first` and the current prefix of `second`.
+    let mut distances: Vec<_> = (0..=first.len()).collect();
+    for (j, second_char) in second.iter().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = j + 1;
+        for (i, first_char) in first.iter().enumerate() {
+            let substitution = previous + usize::from(first_char != second_char);
+            previous = distances[i + 1];
+            distances[i + 1] = substitution.min(distances[i] + 1).min(distances[i + 1] + 1);
+        }
+    }
+
+    distances[first.len()]
+}
+
+impl HazardCatalog {
+    // Loads the catalog from the file of ascot-library declaring the `Hazard` enum.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let source_code = read_file(path)?;
+        let ast = Tree::new::<RustCode>(&source_code);
+
+        let root = ast.get_root();
+
+        let variants = hazard_variants(root, &source_code).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Analysis,
+                format!("No `{HAZARD_ENUM}` enum in {}", path.display()),
+            )
+        })?;
+
+        let ids = method_arms(root, ID_METHOD, &source_code);
+        let descriptions = method_arms(root, DESCRIPTION_METHOD, &source_code);
+        let categories = method_arms(root, CATEGORY_METHOD, &source_code);
+
+        let hazards = variants
+            .into_iter()
+            .map(|(name, doc)| {
+                let description = descriptions
+                    .get(name)
+                    .map(|description| description.to_string())
+                    .or_else(|| (!doc.is_empty()).then_some(doc));
+                let info = HazardInfo {
+                    id: ids.get(name).and_then(|id| id.parse().ok()),
+                    description,
+                    category: categories.get(name).map(|category| category.to_string()),
+                };
+                (name.to_string(), info)
+            })
+            .collect();
+
+        Ok(Self { hazards })
+    }
+
+    #[inline(always)]
+    // Returns `true` if `hazard` is a variant of the `Hazard` enum.
+    pub(crate) fn contains(&self, hazard: &str) -> bool {
+        self.hazards.contains_key(hazard)
+    }
+
+    #[inline(always)]
+    // Returns the category of `hazard`, if any.
+    pub(crate) fn category(&self, hazard: &str) -> Option<&str> {
+        self.hazards.get(hazard)?.category.as_deref()
+    }
+
+    // Retrieves the descriptions of the valid hazards among `hazards`, sorted by name.
+    pub(crate) fn describe<'a>(
+        &'a self,
+        hazards: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<HazardDescription<'a>> {
+        let hazards: BTreeSet<_> = hazards.into_iter().collect();
+
+        hazards
+            .into_iter()
+            .filter_map(|name| {
+                let (name, info) = self.hazards.get_key_value(name)?;
+                Some(HazardDescription { name, info })
+            })
+            .collect()
+    }
+
+    // Groups the valid hazards among `hazards` by category.
+    pub(crate) fn categorize<'a>(
+        &'a self,
+        hazards: impl IntoIterator<Item = &'a str>,
+    ) -> BTreeMap<&'a str, BTreeSet<&'a str>> {
+        let mut categories = BTreeMap::<_, BTreeSet<_>>::new();
+        for hazard in hazards {
+            if let Some(category) = self.category(hazard) {
+                categories.entry(category).or_default().insert(hazard);
+            }
+        }
+
+        categories
+    }
+
+    // Returns the valid hazard name closest to `hazard`,
+    // if it differs from `hazard` by at most a third of its characters.
+    pub(crate) fn suggestion(&self, hazard: &str) -> Option<&str> {
+        let max_distance = (hazard.len() / 3).max(1);
+
+        self.hazards
+            .keys()
+            .map(|valid| (edit_distance(hazard, valid), valid))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, valid)| valid.as_str())
+    }
+
+    // Retrieves the hazards which are not variants of the `Hazard` enum, sorted by name.
+    pub(crate) fn unknown_hazards<'a>(
+        &'a self,
+        hazards: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<UnknownHazard<'a>> {
+        let unknown: BTreeSet<_> = hazards
+            .into_iter()
+            .filter(|hazard| !self.contains(hazard))
+            .collect();
+
+        unknown
+            .into_iter()
+            .map(|name| UnknownHazard {
+                name,
+                suggestion: self.suggestion(name),
+            })
+            .collect()
+    }
+
+    // Retrieves the unknown hazards used inside the definitions of the Ascot devices,
+    // either as allowed hazards or as mandatory hazards, sorted by device name.
+    pub(crate) fn unknown_device_hazards<'a>(
+        &'a self,
+        ascot_devices: &'a [AscotDevice<'a>],
+    ) -> Vec<UnknownDeviceHazards<'a>> {
+        let mut unknown: Vec<_> = ascot_devices
+            .iter()
+            .filter_map(|device| {
+                let mandatory_hazards: Vec<&HashSet<&str>> = match &device.mandatory_actions {
+                    MandatoryActions::New(actions) => {
+                        actions.values().map(|action| &action.hazards).collect()
+                    }
+                    MandatoryActions::Methods(actions) => {
+                        actions.iter().map(|action| &action.hazards).collect()
+                    }
+                };
+                let hazards = mandatory_hazards
+                    .into_iter()
+                    .flatten()
+                    .chain(&device.allowed_hazards)
+                    .copied();
+
+                let hazards = self.unknown_hazards(hazards);
+                (!hazards.is_empty()).then_some(UnknownDeviceHazards {
+                    device: device.name,
+                    hazards,
+                })
+            })
+            .collect();
+        unknown.sort_by_key(|device| device.device);
+
+        unknown
+    }
+}
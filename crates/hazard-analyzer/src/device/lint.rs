@@ -0,0 +1,299 @@
+// This module defines the consistency checks performed
+// on the device definitions, such as the mandatory hazards
+// which are not included in the allowed hazards of a device.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::Path,
+};
+
+use rust_code_analysis::{Node, Rust, Search};
+use serde::Serialize;
+
+use crate::re::hazards_from_text;
+
+use super::{
+    find_function, hazards_from_value, method_call, methods::get_actions_variants, search_const,
+    AscotDevice, DeviceReport, ALLOWED_HAZARDS, NEW_FUNCTION,
+};
+
+// Set storing the mandatory actions of a `Methods` style device.
+const MANDATORY_ACTIONS: &str = "mandatory_actions";
+
+// Method adding an action to the mandatory actions set.
+const INSERT_METHOD: &str = "insert";
+
+// LintIssue.
+//
+// Inconsistency found inside a device definition.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum LintIssue<'a> {
+    // Expected function which is missing, such as the method named after an `Actions` variant.
+    MissingFunction {
+        device: &'a str,
+        function: String,
+    },
+    // Expected const which is missing, such as the const named after a mandatory action.
+    MissingConst {
+        device: &'a str,
+        constant: String,
+    },
+    // Mandatory hazard of an action which is not included in `ALLOWED_HAZARDS`.
+    HazardNotAllowed {
+        device: &'a str,
+        action: Cow<'a, str>,
+        hazard: &'a str,
+    },
+    // `Actions` variant which is not inserted into the mandatory actions inside new().
+    ActionNotInserted {
+        device: &'a str,
+        variant: &'a str,
+    },
+    // File const read by several device structs, which do not define their own.
+    SharedConst {
+        constant: String,
+        devices: Vec<&'a str>,
+    },
+    // Device file which cannot be read.
+    UnreadableFile {
+        error: &'a str,
+    },
+    // Hazard const which is never referenced.
+    UnusedConst {
+        constant: &'a str,
+    },
+}
+
+// Inconsistencies found inside a device file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeviceFileLints<'a> {
+    // File path.
+    pub(crate) file: &'a Path,
+    // Inconsistencies, ordered by device.
+    pub(crate) issues: Vec<LintIssue<'a>>,
+}
+
+// LintManifest.
+//
+// Outcome of the consistency checks of the device definitions.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LintManifest<'a> {
+    // Whether no inconsistency has been found.
+    pub(crate) consistent: bool,
+    // Device files containing inconsistencies, sorted by path.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) files: &'a [DeviceFileLints<'a>],
+}
+
+// Retrieves the `Actions` variants inserted into the mandatory actions
+// through `mandatory_actions.insert(Actions::..)` calls inside `function`.
+fn inserted_variants<'a>(function: Node<'a>, source_code: &'a [u8]) -> HashSet<&'a str> {
+    function
+        .all_occurrences(|occ| {
+            occ.kind_id() == Rust::CallExpression
+                && method_call(occ, source_code).map_or(false, |(value, method)| {
+                    // `mandatory_actions` or `self.mandatory_actions`.
+                    let receiver = match Rust::from(value.kind_id()) {
+                        Rust::FieldExpression => value.child(value.child_count() - 1),
+                        _ => Some(value),
+                    };
+                    method == INSERT_METHOD
+                        && receiver.and_then(|receiver| receiver.utf8_text(source_code))
+                            == Some(MANDATORY_ACTIONS)
+                })
+        })
+        .into_iter()
+        .filter_map(|call| call.first_child(|child| child.kind_id() == Rust::Arguments))
+        .flat_map(|arguments| {
+            arguments.all_occurrences(|occ| occ.kind_id() == Rust::ScopedIdentifier)
+        })
+        .filter_map(|path| path.utf8_text(source_code)?.strip_prefix("Actions::"))
+        .collect()
+}
+
+// Checks the consistency of a device struct, given its report
+// and the `AscotDevice` produced from it.
+//
+// - `actions_enum`: `Actions` enum of a `Methods` style device.
+pub(crate) fn lint_device<'a>(
+    report: &DeviceReport<'a>,
+    ascot_device: Option<&AscotDevice<'a>>,
+    actions_enum: Option<Node<'a>>,
+    scope: &[Node<'a>],
+    consts: &[Node<'a>],
+    source_code: &'a [u8],
+) -> Vec<LintIssue<'a>> {
+    let device = report.name;
+
+    // Missing functions and `ALLOWED_HAZARDS` const.
+    let mut issues: Vec<_> = report
+        .missing_functions
+        .iter()
+        .map(|function| LintIssue::MissingFunction {
+            device,
+            function: function.clone(),
+        })
+        .chain(
+            report
+                .missing_constants
+                .iter()
+                .map(|constant| LintIssue::MissingConst {
+                    device,
+                    constant: constant.to_string(),
+                }),
+        )
+        .collect();
+
+    // Each mandatory action must have a const named after it
+    // and hazards included in the allowed ones.
+    if let Some(ascot_device) = ascot_device {
+        for action in ascot_device.mandatory_actions.sorted() {
+            let constant = action.name.to_ascii_uppercase();
+            if search_const(consts, &constant, source_code).is_none() {
+                issues.push(LintIssue::MissingConst { device, constant });
+            }
+
+            let not_allowed: BTreeSet<_> = action
+                .hazards
+                .difference(&ascot_device.allowed_hazards)
+                .copied()
+                .collect();
+            issues.extend(
+                not_allowed
+                    .into_iter()
+                    .map(|hazard| LintIssue::HazardNotAllowed {
+                        device,
+                        action: action.name.clone(),
+                        hazard,
+                    }),
+            );
+        }
+    }
+
+    // Each `Actions` variant must be inserted into the mandatory actions inside new().
+    if let Some(actions_enum) = actions_enum {
+        let inserted = find_function(scope, NEW_FUNCTION, source_code)
+            .map(|new_function| inserted_variants(new_function, source_code))
+            .unwrap_or_default();
+        issues.extend(
+            get_actions_variants(actions_enum, source_code)
+                .into_iter()
+                .filter(|variant| !inserted.contains(variant))
+                .map(|variant| LintIssue::ActionNotInserted { device, variant }),
+        );
+    }
+
+    issues
+}
+
+// Retrieves the consts which a device struct reads from the whole file,
+// because its impl blocks do not define them.
+pub(crate) fn file_consts(
+    ascot_device: &AscotDevice,
+    impls: &[Node],
+    root: Node,
+    source_code: &[u8],
+) -> Vec<String> {
+    std::iter::once(ALLOWED_HAZARDS.to_string())
+        .chain(
+            ascot_device
+                .mandatory_actions
+                .sorted()
+                .into_iter()
+                .map(|action| action.name.to_ascii_uppercase()),
+        )
+        .filter(|constant| {
+            search_const(impls, constant, source_code).is_none()
+                && search_const(&[root], constant, source_code).is_some()
+        })
+        .collect()
+}
+
+// Retrieves the file consts read by more than one device struct.
+//
+// - `readers`: device structs reading each file const.
+pub(crate) fn lint_shared_consts<'a>(
+    readers: BTreeMap<String, Vec<&'a str>>,
+) -> Vec<LintIssue<'a>> {
+    readers
+        .into_iter()
+        .filter(|(_, devices)| devices.len() > 1)
+        .map(|(constant, devices)| LintIssue::SharedConst { constant, devices })
+        .collect()
+}
+
+// Returns `true` if `node` is inside a const definition.
+fn is_inside_const(node: Node) -> bool {
+    let mut parent = node.parent();
+    while let Some(node) = parent {
+        if node.kind_id() == Rust::ConstItem {
+            return true;
+        }
+        parent = node.parent();
+    }
+
+    false
+}
+
+// Retrieves the hazard consts of a device file which are never used.
+//
+// A const is used when it is `ALLOWED_HAZARDS`, when it is named after
+// a mandatory action, when it is referenced outside the const definitions,
+// or when it is composed into a used const, such as `BASE_HAZARDS`
+// in `const TURN_ON: &[Hazard] = &[..BASE_HAZARDS, Hazard::FireHazard];`.
+pub(crate) fn lint_unused_consts<'a>(
+    root: Node<'a>,
+    ascot_devices: &[AscotDevice],
+    source_code: &'a [u8],
+) -> Vec<LintIssue<'a>> {
+    let action_consts: HashSet<_> = ascot_devices
+        .iter()
+        .flat_map(|ascot_device| ascot_device.mandatory_actions.sorted())
+        .map(|action| action.name.to_ascii_uppercase())
+        .collect();
+
+    // Names referenced outside the const definitions, such as inside functions.
+    let referenced: HashSet<_> = root
+        .all_occurrences(|occ| occ.kind_id() == Rust::Identifier)
+        .into_iter()
+        .filter(|identifier| !is_inside_const(*identifier))
+        .filter_map(|identifier| identifier.utf8_text(source_code))
+        .collect();
+
+    let consts: Vec<_> = root
+        .all_occurrences(|occ| occ.kind_id() == Rust::ConstItem)
+        .into_iter()
+        .filter_map(|const_item| {
+            let name = const_item
+                .first_child(|child| child.kind_id() == Rust::Identifier)?
+                .utf8_text(source_code)?;
+            Some((name, const_item))
+        })
+        .collect();
+
+    // Follow the consts composed into the used ones.
+    let mut used = HashSet::new();
+    for (name, const_item) in &consts {
+        let is_used =
+            *name == ALLOWED_HAZARDS || action_consts.contains(*name) || referenced.contains(name);
+        if is_used && used.insert(*name) {
+            hazards_from_value(&[root], *const_item, source_code, &mut used);
+        }
+    }
+
+    consts
+        .into_iter()
+        .filter(|(name, _)| !used.contains(name))
+        .filter_map(|(name, const_item)| {
+            // The value is the last child before the `;` token.
+            let value = const_item.child(const_item.child_count().checked_sub(2)?)?;
+            let hazards = hazards_from_text(value.utf8_text(source_code)?)?;
+
+            (!hazards.is_empty()).then_some(LintIssue::UnusedConst { constant: name })
+        })
+        .collect()
+}
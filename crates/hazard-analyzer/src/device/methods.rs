@@ -5,8 +5,8 @@
 use rust_code_analysis::{Node, Rust, Search};
 
 use super::{
-    get_allowed_hazards, hazards_from_const, node_where_child, AscotDevice, DeviceAction,
-    MandatoryActions,
+    find_function, get_allowed_hazards, get_device_action, get_parameters, node_where_child,
+    AscotDevice, DeviceAction, MandatoryActions,
 };
 
 // Retrieves `Actions` enum.
@@ -39,28 +39,43 @@ fn to_snake_case(enum_variant: &str) -> String {
     action
 }
 
-// Retrieves all the mandatory actions names by parsing the `Actions` enum.
-fn get_actions_names(actions_enum: Node, source_code: &[u8]) -> Vec<String> {
+// Retrieves the variants of the `Actions` enum.
+pub(crate) fn get_actions_variants<'a>(
+    actions_enum: Node<'a>,
+    source_code: &'a [u8],
+) -> Vec<&'a str> {
     actions_enum
         .all_occurrences(|occ| occ.kind_id() == Rust::Identifier)
         .into_iter()
-        .filter_map(|identifier| identifier.utf8_text(source_code).map(to_snake_case))
+        .filter_map(|identifier| identifier.utf8_text(source_code))
+        .collect()
+}
+
+// Retrieves all the mandatory actions names by parsing the `Actions` enum.
+pub(crate) fn get_actions_names(actions_enum: Node, source_code: &[u8]) -> Vec<String> {
+    get_actions_variants(actions_enum, source_code)
+        .into_iter()
+        .map(to_snake_case)
         .collect()
 }
 
 fn get_mandatory_actions<'a>(
-    root: Node<'a>,
+    consts: &[Node<'a>],
+    scope: &[Node<'a>],
     actions_names: Vec<String>,
     source_code: &'a [u8],
 ) -> Vec<DeviceAction<'a>> {
     actions_names
         .into_iter()
-        .filter_map(|name| {
-            let hazards = hazards_from_const(root, &name.to_ascii_uppercase(), source_code)?;
-            Some(DeviceAction {
-                name: name.into(),
-                hazards,
-            })
+        .map(|name| {
+            // Search the mandatory hazards checked on the action parameter
+            // inside the method named after the action.
+            let method = find_function(scope, &name, source_code);
+            let parameter =
+                method.and_then(|method| get_parameters(method, source_code).first().copied());
+            let check = method.zip(parameter);
+
+            get_device_action(consts, name.into(), check, source_code)
         })
         .collect()
 }
@@ -69,16 +84,17 @@ fn get_mandatory_actions<'a>(
 // methods to define the mandatory actions.
 pub(crate) fn handle<'a>(
     device_name: &'a str,
-    root: Node<'a>,
+    consts: &[Node<'a>],
+    scope: &[Node<'a>],
     actions_enum: Node,
     source_code: &'a [u8],
 ) -> Option<AscotDevice<'a>> {
     // // Get allowed hazards.
-    let allowed_hazards = get_allowed_hazards(root, source_code)?;
+    let allowed_hazards = get_allowed_hazards(consts, source_code)?;
 
     // Get mandatory actions.
     let actions_names = get_actions_names(actions_enum, source_code);
-    let mandatory_actions = get_mandatory_actions(root, actions_names, source_code);
+    let mandatory_actions = get_mandatory_actions(consts, scope, actions_names, source_code);
 
     Some(AscotDevice {
         name: device_name,
@@ -1,20 +1,35 @@
 pub(crate) mod file;
+pub(crate) mod hazards;
+pub(crate) mod lint;
 mod methods;
 mod new;
 
 use std::{
     borrow::Cow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
 };
 
 use code_certifier::error::Result;
 use crossbeam::channel::{Receiver, Sender};
 use file::DeviceFile;
-use methods::find_actions_enum;
+use lint::{
+    file_consts, lint_device, lint_shared_consts, lint_unused_consts, DeviceFileLints, LintIssue,
+};
+use methods::{find_actions_enum, get_actions_names};
 use rust_code_analysis::{Node, Rust, Search};
 use serde::Serialize;
 
-use crate::{concurrent::ConcurrentRunner, re::HAZARD_RE};
+use crate::{concurrent::ConcurrentRunner, re::hazards_from_text};
+
+// Methods of `DeviceAction` checking the mandatory hazards of an action.
+const HAZARD_CHECKS: &[&str] = &["miss_hazard", "miss_hazards"];
+
+// Const defining the allowed hazards of a device.
+const ALLOWED_HAZARDS: &str = "ALLOWED_HAZARDS";
+
+// Function defining the mandatory actions of a `New` style device.
+const NEW_FUNCTION: &str = "new";
 
 // Mandatory actions that must be performed by an Ascot device.
 #[derive(Debug, Serialize)]
@@ -25,6 +40,21 @@ pub(crate) enum MandatoryActions<'a> {
     Methods(Vec<DeviceAction<'a>>),
 }
 
+impl<'a> MandatoryActions<'a> {
+    // Returns the mandatory actions, sorted by `new()` parameter
+    // or by `Actions` variant.
+    pub(crate) fn sorted(&self) -> Vec<&DeviceAction<'a>> {
+        match self {
+            Self::New(actions) => {
+                let mut actions: Vec<_> = actions.iter().collect();
+                actions.sort_by_key(|(index, _)| **index);
+                actions.into_iter().map(|(_, action)| action).collect()
+            }
+            Self::Methods(actions) => actions.iter().collect(),
+        }
+    }
+}
+
 // Ascot device action.
 #[derive(Debug, Serialize)]
 pub(crate) struct DeviceAction<'a> {
@@ -32,6 +62,97 @@ pub(crate) struct DeviceAction<'a> {
     pub(crate) name: Cow<'a, str>,
     // Hazards of the action.
     pub(crate) hazards: HashSet<&'a str>,
+    // Hazards of the const named after the action, if they differ
+    // from the hazards checked through `miss_hazard` or `miss_hazards`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) const_hazards: Option<HashSet<&'a str>>,
+    // Where the hazards of the action have been found.
+    #[serde(skip)]
+    pub(crate) hazards_source: HazardsSource,
+}
+
+// Source of the mandatory hazards of an action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum HazardsSource {
+    // `miss_hazard` or `miss_hazards` checks.
+    Check,
+    // Const named after the action.
+    Const,
+    // Neither a check nor a const.
+    Missing,
+}
+
+// Style through which a device defines its mandatory actions.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DeviceStyle {
+    // Mandatory actions as parameters of the new() function.
+    New,
+    // Mandatory actions as methods named after the `Actions` enum variants.
+    Methods,
+}
+
+// Mandatory action whose hazards could not be found.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UnresolvedAction<'a> {
+    // Action name.
+    pub(crate) action: Cow<'a, str>,
+    // Name of the missing hazard const.
+    pub(crate) constant: String,
+}
+
+// DeviceReport.
+//
+// Outcome of the analysis of a device struct.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeviceReport<'a> {
+    // Device name.
+    pub(crate) name: &'a str,
+    // Style of the device.
+    pub(crate) style: DeviceStyle,
+    // Whether an Ascot device has been produced.
+    pub(crate) recognized: bool,
+    // Expected consts which are missing.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) missing_constants: Vec<&'static str>,
+    // Expected functions which are missing.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) missing_functions: Vec<String>,
+    // Mandatory actions whose hazards could not be found.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) unresolved_actions: Vec<UnresolvedAction<'a>>,
+}
+
+// Outcome of the analysis of a device file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeviceFileReport<'a> {
+    // File path.
+    pub(crate) file: &'a Path,
+    // Error raised while reading the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<&'a str>,
+    // Devices found inside the file.
+    pub(crate) devices: Vec<DeviceReport<'a>>,
+}
+
+// HazardMismatch.
+//
+// Mandatory action whose checked hazards differ
+// from the hazards of the const named after it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HazardMismatch<'a> {
+    // Device name.
+    pub(crate) device: &'a str,
+    // Action name.
+    pub(crate) action: &'a str,
+    // Hazards checked through `miss_hazard` or `miss_hazards`, sorted by name.
+    pub(crate) checked_hazards: Vec<&'a str>,
+    // Hazards of the const named after the action, sorted by name.
+    pub(crate) const_hazards: Vec<&'a str>,
 }
 
 // Ascot device.
@@ -71,48 +192,196 @@ fn node_where_child(
             .is_some()
 }
 
-// Retrieves the new() function instance of the device impl.
+// Retrieves the function named `name` inside the nodes of `scope`.
 #[inline(always)]
-fn get_new_function<'a>(root: Node<'a>, source_code: &'a [u8]) -> Option<Node<'a>> {
-    root.first_occurence(|n| {
-        node_where_child(n, Rust::FunctionItem, Rust::Identifier, "new", source_code)
+fn find_function<'a>(scope: &[Node<'a>], name: &str, source_code: &'a [u8]) -> Option<Node<'a>> {
+    scope.iter().find_map(|node| {
+        node.first_occurence(|n| {
+            node_where_child(n, Rust::FunctionItem, Rust::Identifier, name, source_code)
+        })
     })
 }
 
-// Given a text correspondonding to a piece of code of the file, retrieve all the hazards contained in it.
-fn hazards_from_text(text: &str) -> Option<HashSet<&str>> {
-    let hazards = HAZARD_RE
-        .captures_iter(text)
-        .filter_map(|capture| capture.get(1).map(|re_match| re_match.as_str()))
-        .collect::<HashSet<_>>();
+// Device struct defined inside a device file.
+struct DeviceStruct<'a> {
+    // Struct name.
+    name: &'a str,
+    // Inherent impl blocks of the struct.
+    impls: Vec<Node<'a>>,
+}
 
-    Some(hazards)
+// Returns the name of the type implemented by an inherent impl block,
+// such as `Light` for `impl<S> Light<S>`.
+fn impl_type_name<'a>(impl_item: Node<'a>, source_code: &'a [u8]) -> Option<&'a str> {
+    // Skip trait implementations.
+    if impl_item
+        .first_child(|c| c.utf8_text(source_code) == Some("for"))
+        .is_some()
+    {
+        return None;
+    }
+
+    let impl_type = impl_item.first_child(|c| {
+        matches!(
+            Rust::from(c.kind_id()),
+            Rust::TypeIdentifier | Rust::GenericType
+        )
+    })?;
+    let impl_type = if impl_type.kind_id() == Rust::GenericType {
+        impl_type.first_child(|c| c.kind_id() == Rust::TypeIdentifier)?
+    } else {
+        impl_type
+    };
+
+    impl_type.utf8_text(source_code)
 }
 
-// Search the file for a const with name equal to the given `name`.
-fn search_const<'a>(root: Node<'a>, name: &str, source_code: &'a [u8]) -> Option<&'a str> {
-    // Search the first node occurence of type ConstItem that contains `name` in its name.
-    root.first_occurence(|occ| {
-        occ.kind_id() == Rust::ConstItem
-            && occ
-                .utf8_text(source_code)
-                .map_or(false, |const_text| const_text.contains(name))
+// Retrieves the public device structs of a file, that is the structs
+// with a `Device<S>` field or constructing a `DeviceKind::..` in their impl blocks.
+fn get_device_structs<'a>(root: Node<'a>, source_code: &'a [u8]) -> Vec<DeviceStruct<'a>> {
+    let impls = root.all_occurrences(|n| n.kind_id() == Rust::ImplItem);
+
+    root.all_occurrences(|n| {
+        n.kind_id() == Rust::StructItem
+            && n.first_child(|c| c.kind_id() == Rust::VisibilityModifier)
+                .and_then(|visibility| visibility.utf8_text(source_code))
+                .map_or(false, |visibility| visibility == "pub")
     })
-    .and_then(|const_action| const_action.utf8_text(source_code))
+    .into_iter()
+    .filter_map(|struct_item| {
+        let name = struct_item
+            .first_child(|c| c.kind_id() == Rust::TypeIdentifier)?
+            .utf8_text(source_code)?;
+        let impls: Vec<_> = impls
+            .iter()
+            .copied()
+            .filter(|impl_item| impl_type_name(*impl_item, source_code) == Some(name))
+            .collect();
+
+        // Search a field of type `Device` or `Device<S>`.
+        let has_device_field = struct_item
+            .all_occurrences(|n| n.kind_id() == Rust::FieldDeclaration)
+            .into_iter()
+            .filter_map(|field| field.child(field.child_count().checked_sub(1)?))
+            .filter_map(|field_type| field_type.utf8_text(source_code))
+            .any(|field_type| field_type == "Device" || field_type.starts_with("Device<"));
+
+        // Search a `DeviceKind::..` construction.
+        let has_device_kind = impls.iter().any(|impl_item| {
+            impl_item
+                .first_occurence(|n| {
+                    n.kind_id() == Rust::ScopedIdentifier
+                        && n.utf8_text(source_code)
+                            .map_or(false, |path| path.starts_with("DeviceKind::"))
+                })
+                .is_some()
+        });
+
+        (has_device_field || has_device_kind).then_some(DeviceStruct { name, impls })
+    })
+    .collect()
+}
+
+// Retrieves the names of the parameters of a function.
+fn get_parameters<'a>(function: Node<'a>, source_code: &'a [u8]) -> Vec<&'a str> {
+    // Find the Parameters node.
+    let parameters = function.first_child(|child| child.kind_id() == Rust::Parameters);
+
+    // For each Parameter get its Identifier.
+    parameters
+        .map_or(Vec::new(), |parameters| {
+            parameters.all_occurrences(|occ| occ.kind_id() == Rust::Parameter)
+        })
+        .into_iter()
+        .filter_map(|parameter| {
+            parameter
+                .first_child(|child| child.kind_id() == Rust::Identifier)
+                .and_then(|identifier| identifier.utf8_text(source_code))
+        })
+        .collect()
+}
+
+// Search the nodes of `consts`, in order, for a const with name equal to the given `name`,
+// so that the consts of the impl blocks of a struct take precedence over the file ones.
+fn search_const<'a>(consts: &[Node<'a>], name: &str, source_code: &'a [u8]) -> Option<Node<'a>> {
+    consts.iter().find_map(|node| {
+        node.first_occurence(|occ| {
+            node_where_child(occ, Rust::ConstItem, Rust::Identifier, name, source_code)
+        })
+    })
+}
+
+// Retrieves the hazards of an expression, following the consts it references,
+// such as `BASE_HAZARDS` in `&[..BASE_HAZARDS, Hazard::FireHazard]`.
+//
+// - `visited`: names of the consts already resolved, to avoid cycles.
+fn hazards_from_expression<'a: 'b, 'b>(
+    consts: &[Node<'a>],
+    expression: Node<'a>,
+    source_code: &'a [u8],
+    visited: &mut HashSet<&'b str>,
+) -> Option<HashSet<&'a str>> {
+    let mut hazards = hazards_from_text(expression.utf8_text(source_code)?)?;
+
+    // Identifiers which are not segments of a path may reference other consts.
+    let identifiers = std::iter::once(expression).chain(expression.all_occurrences(|occ| {
+        occ.kind_id() == Rust::Identifier
+            && occ
+                .parent()
+                .map_or(true, |parent| parent.kind_id() != Rust::ScopedIdentifier)
+    }));
+    for identifier in identifiers.filter(|identifier| identifier.kind_id() == Rust::Identifier) {
+        let Some(name) = identifier.utf8_text(source_code) else {
+            continue;
+        };
+        if !visited.insert(name) {
+            continue;
+        }
+        if let Some(referenced) = search_const(consts, name, source_code) {
+            hazards.extend(hazards_from_value(
+                consts,
+                referenced,
+                source_code,
+                visited,
+            )?);
+        }
+    }
+
+    Some(hazards)
+}
+
+#[inline(always)]
+// Retrieves the hazards of a const value.
+//
+// - `visited`: names of the consts already resolved, to avoid cycles.
+fn hazards_from_value<'a: 'b, 'b>(
+    consts: &[Node<'a>],
+    const_item: Node<'a>,
+    source_code: &'a [u8],
+    visited: &mut HashSet<&'b str>,
+) -> Option<HashSet<&'a str>> {
+    // The value is the last child before the `;` token.
+    let value = const_item.child(const_item.child_count().checked_sub(2)?)?;
+    hazards_from_expression(consts, value, source_code, visited)
 }
 
 // Retrieves all the hazards defined in a const definition.
 fn hazards_from_const<'a>(
-    root: Node<'a>,
+    consts: &[Node<'a>],
     const_name: &str,
     source_code: &'a [u8],
 ) -> Option<HashSet<&'a str>> {
     // Search if the file contains a const definition with name equal to const_name.
-    let action_const = search_const(root, const_name, source_code);
+    let action_const = search_const(consts, const_name, source_code);
 
     // If the const exists then retrieve the hazards from its value.
     let hazards = match action_const {
-        Some(const_text) => hazards_from_text(const_text)?,
+        Some(const_item) => hazards_from_value(
+            consts,
+            const_item,
+            source_code,
+            &mut HashSet::from([const_name]),
+        )?,
         None => HashSet::new(),
     };
 
@@ -121,32 +390,311 @@ fn hazards_from_const<'a>(
 
 #[inline(always)]
 // Retrieves the list of allowed hazards.
-fn get_allowed_hazards<'a>(root: Node<'a>, source_code: &'a [u8]) -> Option<HashSet<&'a str>> {
-    hazards_from_const(root, "ALLOWED_HAZARDS", source_code)
+fn get_allowed_hazards<'a>(consts: &[Node<'a>], source_code: &'a [u8]) -> Option<HashSet<&'a str>> {
+    hazards_from_const(consts, ALLOWED_HAZARDS, source_code)
+}
+
+// Returns the receiver and the method name of a `value.method(..)` call.
+fn method_call<'a>(call: &Node<'a>, source_code: &'a [u8]) -> Option<(Node<'a>, &'a str)> {
+    let callee = call
+        .child(0)
+        .filter(|callee| callee.kind_id() == Rust::FieldExpression)?;
+    let value = callee.child(0)?;
+    let field = callee.child(callee.child_count().checked_sub(1)?)?;
+
+    Some((value, field.utf8_text(source_code)?))
+}
+
+// Retrieves the hazards checked through `miss_hazard` or `miss_hazards` calls
+// on the `action` parameter inside `function`, if there is any check.
+//
+// The checks whose hazards cannot be resolved are skipped.
+fn checked_hazards<'a>(
+    consts: &[Node<'a>],
+    function: Node<'a>,
+    action: &str,
+    source_code: &'a [u8],
+) -> Option<HashSet<&'a str>> {
+    // Search `action.miss_hazard(..)` and `action.miss_hazards(..)` calls.
+    let checks = function.all_occurrences(|occ| {
+        occ.kind_id() == Rust::CallExpression
+            && method_call(occ, source_code).map_or(false, |(value, method)| {
+                value.kind_id() == Rust::Identifier
+                    && value.utf8_text(source_code) == Some(action)
+                    && HAZARD_CHECKS.contains(&method)
+            })
+    });
+    if checks.is_empty() {
+        return None;
+    }
+
+    let mut hazards = HashSet::new();
+    for check in checks {
+        let Some(checked) = check
+            .first_child(|child| child.kind_id() == Rust::Arguments)
+            .and_then(|arguments| {
+                hazards_from_expression(consts, arguments, source_code, &mut HashSet::new())
+            })
+        else {
+            continue;
+        };
+        hazards.extend(checked);
+    }
+
+    Some(hazards)
+}
+
+// Creates a mandatory `DeviceAction`, whose hazards are the ones checked
+// on the `parameter` of `function` or, when there is no check,
+// the ones of the const named after the action.
+fn get_device_action<'a>(
+    consts: &[Node<'a>],
+    name: Cow<'a, str>,
+    check: Option<(Node<'a>, &str)>,
+    source_code: &'a [u8],
+) -> DeviceAction<'a> {
+    let const_name = name.to_ascii_uppercase();
+    let const_hazards = search_const(consts, &const_name, source_code).and_then(|const_item| {
+        hazards_from_value(
+            consts,
+            const_item,
+            source_code,
+            &mut HashSet::from([const_name.as_str()]),
+        )
+    });
+    let checked_hazards = check.and_then(|(function, parameter)| {
+        checked_hazards(consts, function, parameter, source_code)
+    });
+
+    let (hazards, const_hazards, hazards_source) = match (checked_hazards, const_hazards) {
+        (Some(checked), Some(const_hazards)) if checked != const_hazards => {
+            (checked, Some(const_hazards), HazardsSource::Check)
+        }
+        (Some(checked), _) => (checked, None, HazardsSource::Check),
+        (None, Some(const_hazards)) => (const_hazards, None, HazardsSource::Const),
+        (None, None) => (HashSet::new(), None, HazardsSource::Missing),
+    };
+
+    DeviceAction {
+        name,
+        hazards,
+        const_hazards,
+        hazards_source,
+    }
+}
+
+// Retrieves the mandatory actions whose checked hazards differ
+// from the hazards of the const named after them, sorted by device.
+pub(crate) fn hazard_mismatches<'a>(
+    ascot_devices: &'a [AscotDevice<'a>],
+) -> Vec<HazardMismatch<'a>> {
+    let mut mismatches: Vec<_> = ascot_devices
+        .iter()
+        .flat_map(|device| {
+            device
+                .mandatory_actions
+                .sorted()
+                .into_iter()
+                .filter_map(move |action| {
+                    let mut checked_hazards: Vec<_> = action.hazards.iter().copied().collect();
+                    checked_hazards.sort_unstable();
+                    let mut const_hazards: Vec<_> =
+                        action.const_hazards.as_ref()?.iter().copied().collect();
+                    const_hazards.sort_unstable();
+
+                    Some(HazardMismatch {
+                        device: device.name,
+                        action: &action.name,
+                        checked_hazards,
+                        const_hazards,
+                    })
+                })
+        })
+        .collect();
+    mismatches.sort_by_key(|mismatch| mismatch.device);
+
+    mismatches
 }
 
-// Creates an `AscotDevice` starting from a `DeviceFile`.
-fn get_ascot_device(device_file: &DeviceFile) -> Option<AscotDevice> {
+// Creates the report of a device struct, given the `AscotDevice` produced from it.
+fn get_device_report<'a>(
+    name: &'a str,
+    style: DeviceStyle,
+    expected_functions: Vec<String>,
+    ascot_device: Option<&AscotDevice<'a>>,
+    scope: &[Node<'a>],
+    consts: &[Node<'a>],
+    source_code: &'a [u8],
+) -> DeviceReport<'a> {
+    let missing_constants = if search_const(consts, ALLOWED_HAZARDS, source_code).is_none() {
+        vec![ALLOWED_HAZARDS]
+    } else {
+        Vec::new()
+    };
+
+    let missing_functions = expected_functions
+        .into_iter()
+        .filter(|function| find_function(scope, function, source_code).is_none())
+        .collect();
+
+    let unresolved_actions = ascot_device.map_or(Vec::new(), |ascot_device| {
+        ascot_device
+            .mandatory_actions
+            .sorted()
+            .into_iter()
+            .filter(|action| action.hazards_source == HazardsSource::Missing)
+            .map(|action| UnresolvedAction {
+                action: action.name.clone(),
+                constant: action.name.to_ascii_uppercase(),
+            })
+            .collect()
+    });
+
+    DeviceReport {
+        name,
+        style,
+        recognized: ascot_device.is_some(),
+        missing_constants,
+        missing_functions,
+        unresolved_actions,
+    }
+}
+
+// Creates an `AscotDevice` for each device struct of a `DeviceFile`,
+// along with the report of each device struct and the inconsistencies of the file.
+fn get_ascot_devices(
+    device_file: &DeviceFile,
+) -> (Vec<AscotDevice>, DeviceFileReport, DeviceFileLints) {
+    // Report the files which cannot be read without analyzing them.
+    if let Some(error) = &device_file.error {
+        let report = DeviceFileReport {
+            file: &device_file.path,
+            error: Some(error),
+            devices: Vec::new(),
+        };
+        let lints = DeviceFileLints {
+            file: &device_file.path,
+            issues: vec![LintIssue::UnreadableFile { error }],
+        };
+        return (Vec::new(), report, lints);
+    }
+
     // Get file root.
     let root = device_file.root();
+    let source_code = &device_file.source_code;
 
-    // Check if the device uses methods or the new() function to define the actions
+    // Get the device structs, falling back to the device named after the file.
+    let mut device_structs = get_device_structs(root, source_code);
+    if device_structs.is_empty() {
+        device_structs.push(DeviceStruct {
+            name: &device_file.name,
+            impls: Vec::new(),
+        });
+    }
+
+    // Check if the devices use methods or the new() function to define the actions
     // by verifying if it contains `Actions` enum inside the file.
-    let ascot_device = if let Some(actions_enum) = find_actions_enum(root, &device_file.source_code)
-    {
-        // Handle a device that uses methods.
-        methods::handle(
-            &device_file.name,
-            root,
+    let actions_enum = find_actions_enum(root, source_code);
+
+    let mut ascot_devices = Vec::new();
+    let mut reports = Vec::new();
+    let mut issues = Vec::new();
+    let mut const_readers: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for device_struct in device_structs {
+        // Search the hazard consts inside the struct impl blocks before the whole file.
+        let consts: Vec<_> = device_struct
+            .impls
+            .iter()
+            .copied()
+            .chain(std::iter::once(root))
+            .collect();
+
+        // Search the device functions inside the struct impl blocks
+        // or, when there are none, inside the whole file.
+        let scope = if device_struct.impls.is_empty() {
+            vec![root]
+        } else {
+            device_struct.impls
+        };
+
+        let (ascot_device, style, expected_functions) = if let Some(actions_enum) = actions_enum {
+            // Handle a device that uses methods.
+            (
+                methods::handle(
+                    device_struct.name,
+                    &consts,
+                    &scope,
+                    actions_enum,
+                    source_code,
+                ),
+                DeviceStyle::Methods,
+                get_actions_names(actions_enum, source_code),
+            )
+        } else {
+            // Handle a device that uses the new() function.
+            (
+                new::handle(device_struct.name, &consts, &scope, source_code),
+                DeviceStyle::New,
+                vec![NEW_FUNCTION.to_string()],
+            )
+        };
+
+        let report = get_device_report(
+            device_struct.name,
+            style,
+            expected_functions,
+            ascot_device.as_ref(),
+            &scope,
+            &consts,
+            source_code,
+        );
+        issues.extend(lint_device(
+            &report,
+            ascot_device.as_ref(),
             actions_enum,
-            &device_file.source_code,
-        )
-    } else {
-        // Handle a device that uses the new() function.
-        new::handle(&device_file.name, root, &device_file.source_code)
+            &scope,
+            &consts,
+            source_code,
+        ));
+        reports.push(report);
+
+        // The impl blocks of the struct precede the file root.
+        if let Some(ascot_device) = &ascot_device {
+            for constant in
+                file_consts(ascot_device, &consts[..consts.len() - 1], root, source_code)
+            {
+                const_readers
+                    .entry(constant)
+                    .or_default()
+                    .push(device_struct.name);
+            }
+        }
+        ascot_devices.extend(ascot_device);
+    }
+    issues.extend(lint_shared_consts(const_readers));
+    issues.extend(lint_unused_consts(root, &ascot_devices, source_code));
+
+    let report = DeviceFileReport {
+        file: &device_file.path,
+        error: None,
+        devices: reports,
+    };
+    let lints = DeviceFileLints {
+        file: &device_file.path,
+        issues,
     };
 
-    ascot_device
+    (ascot_devices, report, lints)
+}
+
+// Ascot devices extracted from the device files.
+pub(crate) struct AscotDevices<'a> {
+    // Ascot devices.
+    pub(crate) devices: Vec<AscotDevice<'a>>,
+    // Reports of the device files, sorted by path.
+    pub(crate) reports: Vec<DeviceFileReport<'a>>,
+    // Inconsistencies of the device files which contain any, sorted by path.
+    pub(crate) lints: Vec<DeviceFileLints<'a>>,
 }
 
 // DeviceProducer.
@@ -164,8 +712,12 @@ impl DeviceProducer {
 impl<'a> ConcurrentRunner<'a> for DeviceProducer {
     type Items = &'a [DeviceFile];
     type ProducerItem = &'a DeviceFile;
-    type ConsumerItem = AscotDevice<'a>;
-    type Output = Vec<AscotDevice<'a>>;
+    type ConsumerItem = (
+        Vec<AscotDevice<'a>>,
+        DeviceFileReport<'a>,
+        DeviceFileLints<'a>,
+    );
+    type Output = AscotDevices<'a>;
 
     fn producer(
         &self,
@@ -185,21 +737,31 @@ impl<'a> ConcurrentRunner<'a> for DeviceProducer {
         sender: Sender<Self::ConsumerItem>,
     ) -> Result<()> {
         while let Ok(device_file) = receiver.recv() {
-            if let Some(ascot_device) = get_ascot_device(device_file) {
-                sender.send(ascot_device)?;
-            }
+            sender.send(get_ascot_devices(device_file))?;
         }
 
         Ok(())
     }
 
     fn composer(&self, receiver: Receiver<Self::ConsumerItem>) -> Result<Self::Output> {
-        let mut ascot_devices = Vec::new();
-        while let Ok(ascot_device) = receiver.recv() {
-            ascot_devices.push(ascot_device);
+        let mut devices = Vec::new();
+        let mut reports = Vec::new();
+        let mut lints = Vec::new();
+        while let Ok((ascot_devices, report, file_lints)) = receiver.recv() {
+            devices.extend(ascot_devices);
+            reports.push(report);
+            if !file_lints.issues.is_empty() {
+                lints.push(file_lints);
+            }
         }
+        reports.sort_by(|first, second| first.file.cmp(second.file));
+        lints.sort_by(|first, second| first.file.cmp(second.file));
 
-        Ok(ascot_devices)
+        Ok(AscotDevices {
+            devices,
+            reports,
+            lints,
+        })
     }
 }
 
@@ -212,7 +774,7 @@ mod tests {
 
     use crate::concurrent::ConcurrentRunner;
 
-    use super::{AscotDevice, DeviceFile, DeviceProducer};
+    use super::{file::get_device_files, AscotDevice, AscotDevices, DeviceFile, DeviceProducer};
 
     const SNAPSHOTS_PATH: &str = "../../tests/snapshots/devices/";
 
@@ -225,9 +787,29 @@ mod tests {
     const ASCOT_LIGHT: &str = "Light";
     const ASCOT_LIGHT_PATH: &str = "./tests/devices/light.rs";
 
+    const MISSING_DEVICE_PATH: &str = "./tests/devices/missing_device.rs";
+
+    const MISSING_MODULES_PATH: &str = "./tests/devices_with_missing_modules/";
+    const REMOVED_MODULE_PATH: &str = "./tests/devices_with_missing_modules/removed.rs";
+
     const ASCOT_FRIDGE: &str = "Fridge";
     const ASCOT_FRIDGE_PATH: &str = "./tests/devices/fridge.rs";
 
+    const COMPOSED_DEVICE: &str = "ComposedDevice";
+    const COMPOSED_DEVICE_PATH: &str = "./tests/devices_with_composed_hazards/composed_device.rs";
+
+    const OVEN: &str = "Oven";
+    const OVEN_PATH: &str = "./tests/devices_with_hazard_checks/oven.rs";
+
+    const KETTLE: &str = "Kettle";
+    const KETTLE_PATH: &str = "./tests/devices_with_split_checks/kettle.rs";
+
+    const SMART_SOCKET: &str = "SmartSocket";
+    const SMART_SOCKET_PATH: &str = "./tests/devices_with_scoped_consts/smart_socket.rs";
+
+    const SMART_PLUG: &str = "SmartPlug";
+    const SMART_PLUG_PATH: &str = "./tests/devices_with_structs/smart_plug.rs";
+
     struct DeviceData {
         name: String,
         path: &'static Path,
@@ -237,6 +819,7 @@ mod tests {
         DeviceProducer::new()
             .run(&device_files, (rayon::current_num_threads() - 1).max(1))
             .unwrap()
+            .devices
     }
 
     fn set_device_files(device_files: Vec<DeviceData>) -> Vec<DeviceFile> {
@@ -247,8 +830,10 @@ mod tests {
                 let ast = Tree::new::<RustCode>(&source_code);
                 DeviceFile {
                     name: device_data.name,
+                    path: device_data.path.to_path_buf(),
                     source_code,
                     ast,
+                    error: None,
                 }
             })
             .collect()
@@ -379,4 +964,170 @@ mod tests {
                 })
         });
     }
+
+    #[test]
+    fn composed_hazard_consts() {
+        let device_files = set_device_files(vec![DeviceData {
+            name: COMPOSED_DEVICE.to_string(),
+            path: Path::new(COMPOSED_DEVICE_PATH),
+        }]);
+
+        let ascot_devices = set_device_producer(&device_files);
+        let composed_device = ascot_devices.first().unwrap();
+
+        insta::with_settings!({
+            snapshot_path => Path::new(SNAPSHOTS_PATH),
+            prepend_module_to_snapshot => false,
+            sort_maps => true
+        }, {
+            insta::assert_yaml_snapshot!("composed_hazard_consts", composed_device,
+                {
+                    ".allowed_hazards" => sorted_redaction(),
+                    ".mandatory_actions" => sorted_redaction(),
+                    ".mandatory_actions.*.hazards" => sorted_redaction()
+                })
+        });
+    }
+
+    #[test]
+    fn hazard_checks() {
+        let device_files = set_device_files(vec![DeviceData {
+            name: OVEN.to_string(),
+            path: Path::new(OVEN_PATH),
+        }]);
+
+        let ascot_devices = set_device_producer(&device_files);
+        let oven = ascot_devices.first().unwrap();
+
+        insta::with_settings!({
+            snapshot_path => Path::new(SNAPSHOTS_PATH),
+            prepend_module_to_snapshot => false,
+            sort_maps => true
+        }, {
+            insta::assert_yaml_snapshot!("hazard_checks", oven,
+                {
+                    ".allowed_hazards" => sorted_redaction(),
+                    ".mandatory_actions.Methods[].hazards" => sorted_redaction(),
+                    ".mandatory_actions.Methods[].const_hazards" => sorted_redaction()
+                })
+        });
+    }
+
+    #[test]
+    fn split_hazard_checks() {
+        let device_files = set_device_files(vec![DeviceData {
+            name: KETTLE.to_string(),
+            path: Path::new(KETTLE_PATH),
+        }]);
+
+        let ascot_devices = set_device_producer(&device_files);
+        let kettle = ascot_devices.first().unwrap();
+
+        insta::with_settings!({
+            snapshot_path => Path::new(SNAPSHOTS_PATH),
+            prepend_module_to_snapshot => false,
+            sort_maps => true
+        }, {
+            insta::assert_yaml_snapshot!("split_hazard_checks", kettle,
+                {
+                    ".allowed_hazards" => sorted_redaction(),
+                    ".mandatory_actions.Methods[].hazards" => sorted_redaction(),
+                    ".mandatory_actions.Methods[].const_hazards" => sorted_redaction()
+                })
+        });
+    }
+
+    #[test]
+    fn multiple_device_structs() {
+        let device_files = set_device_files(vec![DeviceData {
+            name: SMART_PLUG.to_string(),
+            path: Path::new(SMART_PLUG_PATH),
+        }]);
+
+        let ascot_devices = set_device_producer(&device_files);
+
+        insta::with_settings!({
+            snapshot_path => Path::new(SNAPSHOTS_PATH),
+            prepend_module_to_snapshot => false,
+            sort_maps => true
+        }, {
+            insta::assert_yaml_snapshot!("multiple_device_structs", ascot_devices,
+                {
+                    "." => sorted_redaction(),
+                    "[].allowed_hazards" => sorted_redaction(),
+                    "[].mandatory_actions" => sorted_redaction(),
+                    "[].mandatory_actions.*.hazards" => sorted_redaction(),
+                })
+        });
+    }
+
+    #[test]
+    fn scoped_device_consts() {
+        let device_files = set_device_files(vec![DeviceData {
+            name: SMART_SOCKET.to_string(),
+            path: Path::new(SMART_SOCKET_PATH),
+        }]);
+
+        let ascot_devices = set_device_producer(&device_files);
+
+        insta::with_settings!({
+            snapshot_path => Path::new(SNAPSHOTS_PATH),
+            prepend_module_to_snapshot => false,
+            sort_maps => true
+        }, {
+            insta::assert_yaml_snapshot!("scoped_device_consts", ascot_devices,
+                {
+                    "." => sorted_redaction(),
+                    "[].allowed_hazards" => sorted_redaction(),
+                    "[].mandatory_actions" => sorted_redaction(),
+                    "[].mandatory_actions.*.hazards" => sorted_redaction(),
+                })
+        });
+    }
+
+    #[test]
+    fn unreadable_device_file() {
+        let device_files = vec![
+            DeviceFile::new(Path::new(MISSING_DEVICE_PATH).to_path_buf()),
+            DeviceFile::new(Path::new(ASCOT_LIGHT_PATH).to_path_buf()),
+        ];
+
+        let AscotDevices {
+            devices, reports, ..
+        } = DeviceProducer::new()
+            .run(&device_files, (rayon::current_num_threads() - 1).max(1))
+            .unwrap();
+
+        // The readable file is still analyzed.
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, ASCOT_LIGHT);
+
+        let missing = reports
+            .iter()
+            .find(|report| report.file == Path::new(MISSING_DEVICE_PATH))
+            .unwrap();
+        assert!(missing.error.is_some());
+        assert!(missing.devices.is_empty());
+    }
+
+    #[test]
+    fn missing_device_module() {
+        let device_files = get_device_files(Path::new(MISSING_MODULES_PATH)).unwrap();
+
+        let AscotDevices {
+            devices, reports, ..
+        } = DeviceProducer::new()
+            .run(&device_files, (rayon::current_num_threads() - 1).max(1))
+            .unwrap();
+
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, ASCOT_LIGHT);
+
+        // The declared module whose file is missing is reported.
+        let removed = reports
+            .iter()
+            .find(|report| report.file == Path::new(REMOVED_MODULE_PATH))
+            .unwrap();
+        assert!(removed.error.is_some());
+    }
 }
@@ -0,0 +1,207 @@
+// This module defines heuristics
+// for analyzing the body of an action handler,
+// searching for constructs which are unsafe on a device server,
+// such as `unwrap()` or `std::thread::sleep`.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    path::Path,
+};
+
+use rust_code_analysis::{Node, Rust, Search};
+use serde::Serialize;
+
+use crate::modules::last_segment;
+
+use super::{cfg::is_enabled, symbols::FunctionItem, Context};
+
+// Methods which panic on failure.
+const PANIC_METHODS: &[&str] = &["unwrap", "expect"];
+
+// Macros which panic.
+const PANIC_MACROS: &[&str] = &["panic", "unreachable", "todo", "unimplemented"];
+
+// Paths of the blocking functions of the standard library.
+const BLOCKING_PATHS: &[&str] = &[
+    "std::thread::sleep",
+    "std::fs::",
+    "std::io::stdin",
+    "std::net::",
+    "std::process::",
+];
+
+// Kind of a construct found inside a handler.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FindingKind {
+    // A construct which can panic, such as `unwrap()` or `panic!`.
+    Panic,
+    // A blocking call of the standard library inside an async handler.
+    BlockingCall,
+    // An `unsafe` block.
+    UnsafeBlock,
+}
+
+// HandlerFinding.
+//
+// Construct found inside the body of a handler
+// or of a function of the crate it calls.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HandlerFinding<'a> {
+    // Kind of the construct.
+    pub(crate) kind: FindingKind,
+    // Construct, such as `unwrap` or `std::thread::sleep`.
+    pub(crate) construct: &'a str,
+    // Function containing the construct.
+    pub(crate) function: &'a str,
+    // File containing the construct.
+    pub(crate) file: &'a Path,
+    // Row and column of the construct inside the file.
+    pub(crate) position: (usize, usize),
+}
+
+#[inline(always)]
+// Returns `true` if `path` refers to a blocking function of the standard library.
+fn is_blocking_path(path: &str) -> bool {
+    let path = path.trim_start_matches("::");
+    BLOCKING_PATHS
+        .iter()
+        .any(|blocking| path.starts_with(blocking))
+}
+
+// Construct or call found inside a function body.
+enum BodyNode<'a> {
+    // Construct, along with its row and column inside the file.
+    Finding(FindingKind, &'a str, (usize, usize)),
+    // Call to a function of the crate.
+    Call(&'a FunctionItem<'a>),
+}
+
+// Analyzes a node of a function body.
+fn analyze_node<'a>(
+    node: Node<'a>,
+    context: Context<'a>,
+    async_context: bool,
+) -> Option<BodyNode<'a>> {
+    let source_code = context.source_code();
+
+    match Rust::from(node.kind_id()) {
+        Rust::UnsafeBlock => Some(BodyNode::Finding(
+            FindingKind::UnsafeBlock,
+            "unsafe",
+            node.start_position(),
+        )),
+        // `panic!(..)`.
+        Rust::MacroInvocation => {
+            let name = node.child(0)?.utf8_text(source_code)?;
+            PANIC_MACROS
+                .contains(&last_segment(name))
+                .then(|| BodyNode::Finding(FindingKind::Panic, name, node.start_position()))
+        }
+        Rust::CallExpression => {
+            let function = node.child(0)?;
+
+            match Rust::from(function.kind_id()) {
+                // `value.unwrap()`.
+                Rust::FieldExpression => {
+                    let field = function.child(function.child_count() - 1)?;
+                    let method = field.utf8_text(source_code)?;
+                    PANIC_METHODS.contains(&method).then(|| {
+                        BodyNode::Finding(FindingKind::Panic, method, field.start_position())
+                    })
+                }
+                // `function(..)` or `path::function(..)`.
+                Rust::Identifier | Rust::ScopedIdentifier => {
+                    let path = function.utf8_text(source_code)?;
+                    let path_file = context.firmware_file.path.as_ref();
+
+                    // Expand the path through the file imports.
+                    let full_path = context
+                        .symbols
+                        .imports(path_file)
+                        .and_then(|imports| imports.resolve(path))
+                        .unwrap_or_else(|| path.to_string());
+
+                    if is_blocking_path(&full_path) {
+                        async_context.then(|| {
+                            BodyNode::Finding(
+                                FindingKind::BlockingCall,
+                                path,
+                                node.start_position(),
+                            )
+                        })
+                    } else {
+                        context
+                            .symbols
+                            .function(path_file, path, context.cfg)
+                            .map(BodyNode::Call)
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Analyzes the body of a handler and of the functions of the crate it calls,
+// up to `depth` nested calls, searching for panicking constructs, `unsafe` blocks
+// and, for an async handler, blocking calls of the standard library.
+pub(crate) fn analyze_handler<'a>(
+    handler: &'a FunctionItem<'a>,
+    context: Context<'a>,
+    depth: usize,
+) -> Vec<HandlerFinding<'a>> {
+    let mut findings = Vec::new();
+    let mut visited: HashSet<(&Path, _)> =
+        HashSet::from([(handler.firmware_file.path.as_ref(), handler.position)]);
+    let mut functions = VecDeque::from([(handler, 0)]);
+
+    while let Some((function, level)) = functions.pop_front() {
+        let Some(node) = function.node() else {
+            continue;
+        };
+        let context = Context {
+            firmware_file: function.firmware_file,
+            ..context
+        };
+
+        let mut nodes = node.all_occurrences(|n| {
+            matches!(
+                Rust::from(n.kind_id()),
+                Rust::CallExpression | Rust::MacroInvocation | Rust::UnsafeBlock
+            )
+        });
+        nodes.retain(|n| is_enabled(*n, context.cfg, context.source_code()));
+
+        let mut function_findings = Vec::new();
+        for n in nodes {
+            match analyze_node(n, context, handler.is_async) {
+                Some(BodyNode::Finding(kind, construct, position)) => {
+                    function_findings.push(HandlerFinding {
+                        kind,
+                        construct,
+                        function: function.name,
+                        file: &function.firmware_file.path,
+                        position,
+                    });
+                }
+                // Analyze the called functions up to `depth`.
+                Some(BodyNode::Call(callee))
+                    if level < depth
+                        && visited
+                            .insert((callee.firmware_file.path.as_ref(), callee.position)) =>
+                {
+                    functions.push_back((callee, level + 1));
+                }
+                _ => {}
+            }
+        }
+
+        function_findings.sort_by_key(|finding| finding.position);
+        findings.extend(function_findings);
+    }
+
+    findings
+}
@@ -0,0 +1,277 @@
+// This module defines functions
+// for evaluating the `#[cfg(..)]` attributes of a firmware,
+// such as `#[cfg(all(feature = "eco", not(test)))]`.
+
+use std::collections::{BTreeSet, HashSet};
+
+use rust_code_analysis::{Node, Rust, Search};
+
+use super::{chain::same_node, file::FirmwareFile};
+
+// Active features and cfg options used to evaluate `#[cfg(..)]` attributes.
+#[derive(Debug, Default)]
+pub(crate) struct CfgSet {
+    // Active features.
+    features: HashSet<String>,
+    // Active cfg options, either `name` or `name="value"`.
+    options: HashSet<String>,
+}
+
+// Token of a cfg predicate.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Identifier(&'a str),
+    Literal(&'a str),
+    Equal,
+    Comma,
+    LeftParen,
+    RightParen,
+}
+
+// Splits a cfg predicate into tokens.
+//
+// Returns `None` if the predicate contains unexpected characters.
+fn tokenize(predicate: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = predicate.trim_start();
+
+    while let Some(first) = rest.chars().next() {
+        let (token, length) = match first {
+            '=' => (Token::Equal, 1),
+            ',' => (Token::Comma, 1),
+            '(' => (Token::LeftParen, 1),
+            ')' => (Token::RightParen, 1),
+            '"' => {
+                let end = rest[1..].find('"')?;
+                (Token::Literal(&rest[1..=end]), end + 2)
+            }
+            _ if first.is_alphanumeric() || first == '_' => {
+                let end = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                (Token::Identifier(&rest[..end]), end)
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+#[inline(always)]
+// Normalizes a cfg option, e.g. `target_os = "linux"` into `target_os="linux"`.
+fn normalize_option(option: &str) -> String {
+    match option.split_once('=') {
+        Some((name, value)) => format!("{}=\"{}\"", name.trim(), value.trim().trim_matches('"')),
+        None => option.trim().to_string(),
+    }
+}
+
+impl CfgSet {
+    // Creates a set of active features and cfg options.
+    pub(crate) fn new<F, O>(features: F, options: O) -> Self
+    where
+        F: IntoIterator,
+        F::Item: Into<String>,
+        O: IntoIterator,
+        O::Item: AsRef<str>,
+    {
+        Self {
+            features: features.into_iter().map(Into::into).collect(),
+            options: options
+                .into_iter()
+                .map(|option| normalize_option(option.as_ref()))
+                .collect(),
+        }
+    }
+
+    // Evaluates a cfg predicate, such as `any(feature = "eco", unix)`.
+    //
+    // Predicates which cannot be parsed are considered satisfied,
+    // so that their code is analyzed.
+    pub(crate) fn evaluate(&self, predicate: &str) -> bool {
+        let Some(tokens) = tokenize(predicate) else {
+            return true;
+        };
+        let mut position = 0;
+
+        match self.evaluate_tokens(&tokens, &mut position) {
+            Some(result) if position == tokens.len() => result,
+            _ => true,
+        }
+    }
+
+    // Evaluates the predicate starting at `position`, moving it after the predicate.
+    fn evaluate_tokens(&self, tokens: &[Token], position: &mut usize) -> Option<bool> {
+        let Token::Identifier(name) = tokens.get(*position)? else {
+            return None;
+        };
+        *position += 1;
+
+        match tokens.get(*position) {
+            // `name = "value"`.
+            Some(Token::Equal) => {
+                let Token::Literal(value) = tokens.get(*position + 1)? else {
+                    return None;
+                };
+                *position += 2;
+
+                Some(if *name == "feature" {
+                    self.features.contains(*value)
+                } else {
+                    self.options.contains(&format!("{name}=\"{value}\""))
+                })
+            }
+            // `all(..)`, `any(..)` and `not(..)`.
+            Some(Token::LeftParen) => {
+                *position += 1;
+                let mut results = Vec::new();
+                while tokens.get(*position) != Some(&Token::RightParen) {
+                    results.push(self.evaluate_tokens(tokens, position)?);
+                    if tokens.get(*position) == Some(&Token::Comma) {
+                        *position += 1;
+                    }
+                }
+                *position += 1;
+
+                match *name {
+                    "all" => Some(results.into_iter().all(|result| result)),
+                    "any" => Some(results.into_iter().any(|result| result)),
+                    "not" => match results.as_slice() {
+                        [result] => Some(!result),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            // `name`.
+            _ => Some(self.options.contains(*name)),
+        }
+    }
+
+    #[inline(always)]
+    // Returns `true` if all the cfg predicates are satisfied.
+    pub(crate) fn evaluate_all(&self, predicates: &[&str]) -> bool {
+        predicates.iter().all(|predicate| self.evaluate(predicate))
+    }
+}
+
+#[inline(always)]
+// Retrieves the predicate of a `#[cfg(predicate)]` or `#![cfg(predicate)]` attribute.
+fn cfg_predicate<'a>(attribute: Node, source_code: &'a [u8]) -> Option<&'a str> {
+    let text = attribute.utf8_text(source_code)?;
+    let content = text
+        .trim_start_matches('#')
+        .trim_start_matches('!')
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim();
+
+    content
+        .strip_prefix("cfg")?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+// Retrieves the attributes which apply to `node`, that are the outer
+// attributes preceding it and the inner attributes of its parent.
+fn attributes(node: Node) -> Vec<Node> {
+    let Some(parent) = node.parent() else {
+        return Vec::new();
+    };
+    let siblings: Vec<_> = parent.children().collect();
+
+    // Inner attributes, e.g. `#![cfg(..)]`, apply to the whole parent.
+    let mut attributes: Vec<_> = siblings
+        .iter()
+        .filter(|sibling| sibling.kind_id() == Rust::InnerAttributeItem)
+        .copied()
+        .collect();
+
+    // Outer attributes, e.g. `#[cfg(..)]`, precede the node.
+    if let Some(index) = siblings
+        .iter()
+        .position(|sibling| same_node(sibling, &node))
+    {
+        attributes.extend(
+            siblings[..index]
+                .iter()
+                .rev()
+                .filter(|sibling| {
+                    !matches!(
+                        Rust::from(sibling.kind_id()),
+                        Rust::LineComment | Rust::BlockComment
+                    )
+                })
+                .take_while(|sibling| sibling.kind_id() == Rust::AttributeItem)
+                .copied(),
+        );
+    }
+
+    attributes
+}
+
+// Retrieves the predicates of all the cfg attributes
+// which apply to `node` or to one of its ancestors.
+pub(crate) fn cfg_predicates<'a>(node: Node<'a>, source_code: &'a [u8]) -> Vec<&'a str> {
+    let mut predicates = Vec::new();
+    let mut current = Some(node);
+
+    while let Some(node) = current {
+        predicates.extend(
+            attributes(node)
+                .into_iter()
+                .filter_map(|attribute| cfg_predicate(attribute, source_code)),
+        );
+        current = node.parent();
+    }
+
+    predicates
+}
+
+#[inline(always)]
+// Returns `true` if the code of `node` is enabled by `cfg`.
+//
+// When `cfg` is `None`, cfg attributes are not evaluated and all code is enabled.
+pub(crate) fn is_enabled(node: Node, cfg: Option<&CfgSet>, source_code: &[u8]) -> bool {
+    cfg.map_or(true, |cfg| {
+        cfg.evaluate_all(&cfg_predicates(node, source_code))
+    })
+}
+
+// Retrieves the features which appear inside the cfg attributes of the firmware files.
+pub(crate) fn referenced_features<'a>(firmware_files: &'a [FirmwareFile<'a>]) -> BTreeSet<&'a str> {
+    let mut features = BTreeSet::new();
+
+    for firmware_file in firmware_files {
+        let source_code = &firmware_file.source_code;
+        let predicates = firmware_file
+            .root()
+            .all_occurrences(|n| {
+                matches!(
+                    Rust::from(n.kind_id()),
+                    Rust::AttributeItem | Rust::InnerAttributeItem
+                )
+            })
+            .into_iter()
+            .filter_map(|attribute| cfg_predicate(attribute, source_code));
+
+        for predicate in predicates {
+            let Some(tokens) = tokenize(predicate) else {
+                continue;
+            };
+            for window in tokens.windows(3) {
+                if let [Token::Identifier("feature"), Token::Equal, Token::Literal(feature)] =
+                    window
+                {
+                    features.insert(*feature);
+                }
+            }
+        }
+    }
+
+    features
+}
@@ -0,0 +1,272 @@
+// This module defines a walker
+// for the method chains of a firmware,
+// such as `DeviceName::new(..)?.add_action(..)?.state(..).build()`.
+
+use std::collections::HashSet;
+
+use rust_code_analysis::{Node, Rust, Search};
+
+use super::{
+    cfg::{is_enabled, CfgSet},
+    resolve::{enclosing_function, let_binding},
+};
+
+// Method call of a chain.
+pub(crate) struct MethodCall<'a> {
+    // Method name.
+    pub(crate) name: &'a str,
+    // Arguments of the method call, in positional order.
+    pub(crate) arguments: Vec<Node<'a>>,
+    // Row and column of the method identifier inside the firmware file,
+    // e.g. `new` for `DeviceName::new(..)` and `build` for `.build()`.
+    pub(crate) position: (usize, usize),
+}
+
+// Method chain.
+pub(crate) struct MethodChain<'a> {
+    // Node on which the chain is invoked.
+    //
+    // It is the type path for a chain starting with an associated
+    // function call, e.g. `DeviceName` for `DeviceName::new()`,
+    // otherwise the innermost expression of the chain, e.g. a variable.
+    pub(crate) receiver: Node<'a>,
+    // Method calls, in invocation order.
+    pub(crate) calls: Vec<MethodCall<'a>>,
+    // Variables holding the value of the chain at the end of the enclosing function,
+    // if the chain has been followed through its bindings.
+    pub(crate) variables: HashSet<&'a str>,
+}
+
+impl<'a> MethodChain<'a> {
+    // Returns all method calls with name equal to `name`.
+    pub(crate) fn calls_named<'b>(
+        &'b self,
+        name: &'b str,
+    ) -> impl Iterator<Item = &'b MethodCall<'a>> + 'b {
+        self.calls.iter().filter(move |call| call.name == name)
+    }
+
+    // Returns the first method call with name equal to `name`.
+    #[inline(always)]
+    pub(crate) fn call_named(&self, name: &str) -> Option<&MethodCall<'a>> {
+        self.calls_named(name).next()
+    }
+}
+
+#[inline(always)]
+// Returns `true` if the child of an `Arguments` node is an argument,
+// discarding brackets, commas and comments.
+pub(crate) fn is_argument(node: &Node) -> bool {
+    !matches!(
+        Rust::from(node.kind_id()),
+        Rust::LPAREN | Rust::RPAREN | Rust::COMMA | Rust::LineComment | Rust::BlockComment
+    )
+}
+
+#[inline(always)]
+// Returns `true` if the two nodes span the same piece of code.
+pub(crate) fn same_node(first: &Node, second: &Node) -> bool {
+    first.start_byte() == second.start_byte() && first.end_byte() == second.end_byte()
+}
+
+// Retrieves the argument nodes of a call expression, in positional order.
+pub(crate) fn get_arguments(call: Node) -> Vec<Node> {
+    call.first_child(|child| child.kind_id() == Rust::Arguments)
+        .map_or(Vec::new(), |arguments| {
+            arguments.children().filter(is_argument).collect()
+        })
+}
+
+// Climbs from `node` to the outermost expression of the
+// method chain in which `node` is the innermost receiver.
+pub(crate) fn chain_top(node: Node) -> Node {
+    let mut top = node;
+
+    while let Some(parent) = top.parent() {
+        let is_chained = match Rust::from(parent.kind_id()) {
+            Rust::TryExpression | Rust::AwaitExpression | Rust::ParenthesizedExpression => true,
+            // `top` must be the value of a field expression or
+            // the function of a call expression, not an argument.
+            Rust::FieldExpression | Rust::CallExpression => parent
+                .child(0)
+                .map_or(false, |first| same_node(&first, &top)),
+            _ => false,
+        };
+
+        if !is_chained {
+            break;
+        }
+        top = parent;
+    }
+
+    top
+}
+
+// Walks the method chain whose outermost expression is `expression`.
+pub(crate) fn method_chain<'a>(expression: Node<'a>, source_code: &'a [u8]) -> MethodChain<'a> {
+    let mut calls = Vec::new();
+    let mut node = expression;
+
+    let receiver = loop {
+        match Rust::from(node.kind_id()) {
+            // `expression?` and `expression.await`.
+            Rust::TryExpression | Rust::AwaitExpression => match node.child(0) {
+                Some(inner) => node = inner,
+                None => break node,
+            },
+            // `(expression)`.
+            Rust::ParenthesizedExpression => match node.child(1) {
+                Some(inner) => node = inner,
+                None => break node,
+            },
+            Rust::CallExpression => {
+                let Some(function) = node.child(0) else {
+                    break node;
+                };
+
+                match Rust::from(function.kind_id()) {
+                    // `value.method(..)`.
+                    Rust::FieldExpression => {
+                        let (Some(value), Some(field)) = (
+                            function.child(0),
+                            function.child(function.child_count() - 1),
+                        ) else {
+                            break node;
+                        };
+                        let Some(name) = field.utf8_text(source_code) else {
+                            break node;
+                        };
+
+                        calls.push(MethodCall {
+                            name,
+                            arguments: get_arguments(node),
+                            position: field.start_position(),
+                        });
+                        node = value;
+                    }
+                    // `Path::function(..)`, the first call of the chain.
+                    Rust::ScopedIdentifier => {
+                        let Some(identifier) = function.child(function.child_count() - 1) else {
+                            break node;
+                        };
+                        let Some(name) = identifier.utf8_text(source_code) else {
+                            break node;
+                        };
+
+                        calls.push(MethodCall {
+                            name,
+                            arguments: get_arguments(node),
+                            position: identifier.start_position(),
+                        });
+
+                        // A scoped identifier without a path (`::function`) has only two children.
+                        break if function.child_count() > 2 {
+                            function.child(0).unwrap_or(node)
+                        } else {
+                            node
+                        };
+                    }
+                    _ => break node,
+                }
+            }
+            _ => break node,
+        }
+    };
+
+    // Calls have been collected from the outermost to the innermost one.
+    calls.reverse();
+
+    MethodChain {
+        receiver,
+        calls,
+        variables: HashSet::new(),
+    }
+}
+
+// Retrieves the variable name and the value of a binding,
+// which is either `let variable = value` or `variable = value`.
+fn get_binding<'a>(node: Node<'a>, source_code: &'a [u8]) -> Option<(&'a str, Node<'a>)> {
+    match Rust::from(node.kind_id()) {
+        Rust::LetDeclaration => let_binding(node, source_code),
+        Rust::AssignmentExpression => {
+            let variable = node
+                .child(0)
+                .filter(|left| left.kind_id() == Rust::Identifier)?
+                .utf8_text(source_code)?;
+            let value = node.child(node.child_count() - 1)?;
+
+            Some((variable, value))
+        }
+        _ => None,
+    }
+}
+
+// Walks the method chain of a device instance whose outermost expression is `top`,
+// following the variables the device is bound to inside the enclosing function.
+//
+// Given for example:
+//
+// let mut device = DeviceName::new(..)?;
+// device = device.add_action(..)?;
+// let device = device.state(..).build();
+//
+// all method calls are merged into a single chain, in source order.
+// Bindings which are disabled by `cfg` are skipped.
+pub(crate) fn instance_chain<'a>(
+    top: Node<'a>,
+    source_code: &'a [u8],
+    cfg: Option<&CfgSet>,
+) -> MethodChain<'a> {
+    let mut chain = method_chain(top, source_code);
+
+    // Get the variable the instance is bound to.
+    let Some(binding) = top.parent() else {
+        return chain;
+    };
+    let Some((variable, _)) =
+        get_binding(binding, source_code).filter(|(_, value)| same_node(value, &top))
+    else {
+        return chain;
+    };
+    let Some(function) = enclosing_function(top) else {
+        return chain;
+    };
+
+    // Get all the bindings which follow the instance, in source order.
+    let mut bindings = function.all_occurrences(|n| {
+        matches!(
+            Rust::from(n.kind_id()),
+            Rust::LetDeclaration | Rust::AssignmentExpression
+        )
+    });
+    bindings.retain(|n| n.start_byte() >= binding.end_byte() && is_enabled(*n, cfg, source_code));
+    bindings.sort_by_key(|n| n.start_byte());
+
+    // Variables currently holding the device.
+    let mut variables = HashSet::from([variable]);
+
+    for (target, value) in bindings
+        .into_iter()
+        .filter_map(|n| get_binding(n, source_code))
+    {
+        let next = method_chain(value, source_code);
+
+        // The value is computed starting from a variable holding the device.
+        let continues_chain = next.receiver.kind_id() == Rust::Identifier
+            && next
+                .receiver
+                .utf8_text(source_code)
+                .map_or(false, |receiver| variables.contains(receiver));
+
+        if continues_chain {
+            chain.calls.extend(next.calls);
+            variables.insert(target);
+        } else {
+            // The variable has been bound to another value.
+            variables.remove(target);
+        }
+    }
+    chain.variables = variables;
+
+    chain
+}
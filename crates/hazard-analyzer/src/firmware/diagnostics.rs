@@ -0,0 +1,356 @@
+// This module defines the checks performed
+// on the actions of a device instance,
+// such as two actions registering the same route.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::policy::Policy;
+
+use super::{chain::MethodCall, Handler, MandatoryAction, OptionalAction};
+
+// Location of an action of a device instance.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActionLocation<'a> {
+    // Action name.
+    pub(crate) name: &'a str,
+    // Row and column of the action inside the firmware file.
+    pub(crate) position: (usize, usize),
+}
+
+// Diagnostic.
+//
+// Issue found among the actions of a device instance.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum Diagnostic<'a> {
+    // Actions registering the same HTTP method and path.
+    RouteCollision {
+        method: &'a str,
+        path: &'a str,
+        actions: Vec<ActionLocation<'a>>,
+    },
+    // Actions bound to the same handler.
+    DuplicateHandler {
+        handler: &'a str,
+        actions: Vec<ActionLocation<'a>>,
+    },
+    // Mandatory action added again as an optional action,
+    // with the same handler or the same route.
+    MandatoryActionAsOptional {
+        action: &'a str,
+        position: (usize, usize),
+    },
+    // Method of a mandatory action called more than once.
+    RepeatedMandatoryAction {
+        action: &'a str,
+        actions: Vec<ActionLocation<'a>>,
+    },
+}
+
+// Action of a device instance, either mandatory or optional.
+struct ActionInfo<'a> {
+    // Action location.
+    location: ActionLocation<'a>,
+    // HTTP method and path of the action route.
+    route: Option<(&'a str, &'a str)>,
+    // Action handler.
+    handler: Option<&'a str>,
+}
+
+#[inline(always)]
+// Sorts the locations of a group of actions and keeps the group
+// only if it contains more than one action.
+fn duplicates(mut actions: Vec<ActionLocation>) -> Option<Vec<ActionLocation>> {
+    actions.sort_by_key(|action| action.position);
+    (actions.len() > 1).then_some(actions)
+}
+
+// Returns `true` if the two handlers are the same function.
+//
+// Handlers declared inside the crate are compared by their declaration,
+// the other ones by their name.
+fn same_handler(first: &Handler, second: &Handler) -> bool {
+    match (
+        first.file.zip(first.position),
+        second.file.zip(second.position),
+    ) {
+        (Some(first), Some(second)) => first == second,
+        (None, None) => first.name == second.name,
+        _ => false,
+    }
+}
+
+// Retrieves the diagnostics of the actions of a device instance.
+//
+// - `mandatory_calls`: method calls named after the mandatory actions
+//   of the device, empty if its mandatory actions are passed to `new()`.
+pub(crate) fn get_diagnostics<'a>(
+    mandatory_actions: &[MandatoryAction<'a>],
+    optional_actions: &[OptionalAction<'a>],
+    mandatory_calls: &[&MethodCall<'a>],
+) -> Vec<Diagnostic<'a>> {
+    let actions: Vec<_> = mandatory_actions
+        .iter()
+        .map(|action| ActionInfo {
+            location: ActionLocation {
+                name: action.name,
+                position: action.position,
+            },
+            route: action
+                .route
+                .as_ref()
+                .map(|route| (route.method, route.path)),
+            handler: action.handler.as_ref().map(|handler| handler.name),
+        })
+        .chain(optional_actions.iter().map(|action| {
+            ActionInfo {
+                location: ActionLocation {
+                    name: action.name,
+                    position: action.position,
+                },
+                route: action
+                    .route
+                    .as_ref()
+                    .map(|route| (route.method, route.path)),
+                // The name of an optional action is its handler.
+                handler: Some(action.name),
+            }
+        }))
+        .collect();
+
+    // Group actions by route and by handler.
+    let mut routes = BTreeMap::<_, Vec<_>>::new();
+    let mut handlers = BTreeMap::<_, Vec<_>>::new();
+    for action in actions {
+        if let Some(route) = action.route {
+            routes.entry(route).or_default().push(action.location);
+        }
+        if let Some(handler) = action.handler {
+            handlers.entry(handler).or_default().push(action.location);
+        }
+    }
+
+    let route_collisions = routes.into_iter().filter_map(|((method, path), actions)| {
+        Some(Diagnostic::RouteCollision {
+            method,
+            path,
+            actions: duplicates(actions)?,
+        })
+    });
+
+    let duplicate_handlers = handlers.into_iter().filter_map(|(handler, actions)| {
+        Some(Diagnostic::DuplicateHandler {
+            handler,
+            actions: duplicates(actions)?,
+        })
+    });
+
+    // Optional actions with the handler or the route of a mandatory action.
+    let mut mandatory_as_optional: Vec<_> = optional_actions
+        .iter()
+        .filter_map(|action| {
+            let mandatory_action = mandatory_actions.iter().find(|mandatory_action| {
+                let shares_handler = action
+                    .handler
+                    .as_ref()
+                    .zip(mandatory_action.handler.as_ref())
+                    .map_or(false, |(first, second)| same_handler(first, second));
+                let shares_route = action
+                    .route
+                    .as_ref()
+                    .zip(mandatory_action.route.as_ref())
+                    .map_or(false, |(first, second)| {
+                        (first.method, first.path) == (second.method, second.path)
+                    });
+                shares_handler || shares_route
+            })?;
+            Some((action.position, mandatory_action.name))
+        })
+        .collect();
+    mandatory_as_optional.sort();
+
+    // Group the calls of the mandatory action methods by name.
+    let mut calls = BTreeMap::<_, Vec<_>>::new();
+    for call in mandatory_calls {
+        calls.entry(call.name).or_default().push(ActionLocation {
+            name: call.name,
+            position: call.position,
+        });
+    }
+
+    let repeated_mandatory_actions = calls.into_iter().filter_map(|(action, actions)| {
+        Some(Diagnostic::RepeatedMandatoryAction {
+            action,
+            actions: duplicates(actions)?,
+        })
+    });
+
+    route_collisions
+        .chain(duplicate_handlers)
+        .chain(
+            mandatory_as_optional.into_iter().map(|(position, action)| {
+                Diagnostic::MandatoryActionAsOptional { action, position }
+            }),
+        )
+        .chain(repeated_mandatory_actions)
+        .collect()
+}
+
+// MethodViolation.
+//
+// Action exposing hazards through an HTTP method
+// which is not allowed to by the policy.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MethodViolation<'a> {
+    // Action name.
+    pub(crate) action: &'a str,
+    // Row and column of the action inside the firmware file.
+    pub(crate) position: (usize, usize),
+    // HTTP method of the action route.
+    pub(crate) method: &'a str,
+    // Path of the action route.
+    pub(crate) path: &'a str,
+    // Hazards which cannot be exposed through the method, sorted by name.
+    pub(crate) hazards: Vec<&'a str>,
+}
+
+// Retrieves the actions of a device instance which expose hazards
+// through HTTP methods not allowed by the policy, sorted by position.
+pub(crate) fn get_method_violations<'a>(
+    mandatory_actions: &[MandatoryAction<'a>],
+    optional_actions: &[OptionalAction<'a>],
+    policy: &Policy,
+) -> Vec<MethodViolation<'a>> {
+    let actions = mandatory_actions
+        .iter()
+        .map(|action| (action.name, action.position, &action.route, &action.hazards))
+        .chain(
+            optional_actions
+                .iter()
+                .map(|action| (action.name, action.position, &action.route, &action.hazards)),
+        );
+
+    let mut violations: Vec<_> = actions
+        .filter_map(|(action, position, route, hazards)| {
+            let route = route.as_ref()?;
+            let hazards = policy.forbidden_method_hazards(route.method, hazards);
+
+            (!hazards.is_empty()).then_some(MethodViolation {
+                action,
+                position,
+                method: route.method,
+                path: route.path,
+                hazards,
+            })
+        })
+        .collect();
+    violations.sort_by_key(|violation| violation.position);
+
+    violations
+}
+
+// Reason why an input violates the policy.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum InputViolationKind<'a> {
+    // Range exceeding the bounds allowed by the policy.
+    RangeOutOfBounds {
+        minimum: f64,
+        maximum: f64,
+        #[serde(rename = "allowedMinimum", skip_serializing_if = "Option::is_none")]
+        allowed_minimum: Option<f64>,
+        #[serde(rename = "allowedMaximum", skip_serializing_if = "Option::is_none")]
+        allowed_maximum: Option<f64>,
+    },
+    // Unbounded input where the policy requires a range input,
+    // either because of a range rule or because of the action hazards.
+    UnboundedInput {
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        hazards: Vec<&'a str>,
+    },
+}
+
+// InputViolation.
+//
+// Route input of an action which violates the policy.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InputViolation<'a> {
+    // Action name.
+    pub(crate) action: &'a str,
+    // Input name.
+    pub(crate) input: &'a str,
+    // Row and column of the input inside the file declaring it.
+    pub(crate) position: (usize, usize),
+    // Reason of the violation.
+    #[serde(flatten)]
+    pub(crate) kind: InputViolationKind<'a>,
+}
+
+// Retrieves the route inputs of the actions of a device instance
+// which violate the policy, sorted by position.
+pub(crate) fn get_input_violations<'a>(
+    device: &str,
+    mandatory_actions: &[MandatoryAction<'a>],
+    optional_actions: &[OptionalAction<'a>],
+    policy: &Policy,
+) -> Vec<InputViolation<'a>> {
+    let actions = mandatory_actions
+        .iter()
+        .map(|action| (action.name, &action.route, &action.hazards))
+        .chain(
+            optional_actions
+                .iter()
+                .map(|action| (action.name, &action.route, &action.hazards)),
+        );
+
+    let mut violations = Vec::new();
+    for (action, route, hazards) in actions {
+        let Some(route) = route else {
+            continue;
+        };
+
+        for input in &route.inputs {
+            let mut rules = policy
+                .input_range_rules(device, action, input.name)
+                .peekable();
+            let violation = |kind| InputViolation {
+                action,
+                input: input.name,
+                position: input.position,
+                kind,
+            };
+
+            match &input.range {
+                // Check the range against the bounds of each rule.
+                Some(range) => violations.extend(
+                    rules
+                        .filter(|rule| !rule.allows(range.minimum, range.maximum))
+                        .map(|rule| {
+                            violation(InputViolationKind::RangeOutOfBounds {
+                                minimum: range.minimum,
+                                maximum: range.maximum,
+                                allowed_minimum: rule.minimum,
+                                allowed_maximum: rule.maximum,
+                            })
+                        }),
+                ),
+                // A range input is required by a rule or by the action hazards.
+                None if !input.is_bounded() => {
+                    let hazards = policy.bounded_input_hazards(hazards);
+                    if !hazards.is_empty() || rules.peek().is_some() {
+                        violations.push(violation(InputViolationKind::UnboundedInput { hazards }));
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+    violations.sort_by_key(|violation| violation.position);
+
+    violations
+}
@@ -1,8 +1,20 @@
-use std::{borrow::Cow, path::Path};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 use rust_code_analysis::{read_file, Node, RustCode, Tree};
+use toml::Value;
 
-use code_certifier::error::Result;
+use code_certifier::error::{Error, ErrorKind, Result};
+
+use crate::modules::{get_submodules, normalize_path};
+
+use super::cfg::{cfg_predicates, CfgSet};
+
+// Directory containing the build artifacts of a crate.
+const TARGET_DIRECTORY: &str = "target";
 
 // Firmware file.
 pub(crate) struct FirmwareFile<'a> {
@@ -12,10 +24,13 @@ pub(crate) struct FirmwareFile<'a> {
     pub(crate) source_code: Vec<u8>,
     // File AST.
     ast: Tree,
+    // Predicates of the cfg attributes of the `mod` declarations
+    // through which the file is reached from the root of its crate.
+    pub(crate) cfg: Vec<String>,
 }
 
 impl<'a> FirmwareFile<'a> {
-    fn new(path: Cow<'a, Path>) -> Result<Self> {
+    fn new(path: Cow<'a, Path>, cfg: Vec<String>) -> Result<Self> {
         let source_code = read_file(path.as_ref())?;
         let ast = Tree::new::<RustCode>(&source_code);
 
@@ -23,6 +38,7 @@ impl<'a> FirmwareFile<'a> {
             path,
             source_code,
             ast,
+            cfg,
         })
     }
 
@@ -30,6 +46,24 @@ impl<'a> FirmwareFile<'a> {
     pub(crate) fn root(&self) -> Node {
         self.ast.get_root()
     }
+
+    #[inline(always)]
+    // Returns `true` if the `mod` declarations of the file are enabled by `cfg`.
+    //
+    // When `cfg` is `None`, cfg attributes are not evaluated and all files are enabled.
+    pub(crate) fn is_enabled(&self, cfg: Option<&CfgSet>) -> bool {
+        cfg.map_or(true, |cfg| {
+            self.cfg.iter().all(|predicate| cfg.evaluate(predicate))
+        })
+    }
+}
+
+// Files of a firmware.
+pub(crate) struct FirmwareFiles<'a> {
+    // Files to analyze.
+    pub(crate) files: Vec<FirmwareFile<'a>>,
+    // Files of the firmware crate which are not reachable from its targets.
+    pub(crate) unreachable_files: Vec<PathBuf>,
 }
 
 #[inline(always)]
@@ -37,11 +71,292 @@ fn is_rust_file(path: &Path) -> bool {
     path.extension().and_then(|ext| ext.to_str()) == Some("rs")
 }
 
-pub(crate) fn get_fw_files(fw_path: &Path) -> Result<Vec<FirmwareFile>> {
+#[inline(always)]
+// Returns `true` if `path` is the `Cargo.toml` manifest of a crate.
+fn is_cargo_manifest(path: &Path) -> bool {
+    path.is_file() && path.file_name().and_then(|name| name.to_str()) == Some("Cargo.toml")
+}
+
+// Retrieves the root files of the lib and bin targets of a crate,
+// following the Cargo conventions when a target path is not specified.
+fn get_crate_roots(cargo_toml: &Value, crate_path: &Path) -> Vec<PathBuf> {
+    let src_path = crate_path.join("src");
+    let mut roots = Vec::new();
+
+    // Lib target.
+    let lib_path = cargo_toml
+        .get("lib")
+        .and_then(|lib| lib.get("path"))
+        .and_then(|path| path.as_str())
+        .map_or_else(|| src_path.join("lib.rs"), |path| crate_path.join(path));
+    roots.push(lib_path);
+
+    // Bin targets declared in the manifest.
+    if let Some(bins) = cargo_toml.get("bin").and_then(|bins| bins.as_array()) {
+        for bin in bins {
+            match bin.get("path").and_then(|path| path.as_str()) {
+                Some(path) => roots.push(crate_path.join(path)),
+                None => {
+                    if let Some(name) = bin.get("name").and_then(|name| name.as_str()) {
+                        roots.push(src_path.join("bin").join(format!("{name}.rs")));
+                        roots.push(src_path.join("bin").join(name).join("main.rs"));
+                    }
+                }
+            }
+        }
+    }
+
+    // Bin targets discovered automatically.
+    let autobins = cargo_toml
+        .get("package")
+        .and_then(|package| package.get("autobins"))
+        .and_then(|autobins| autobins.as_bool())
+        .unwrap_or(true);
+    if autobins {
+        roots.push(src_path.join("main.rs"));
+        if let Ok(entries) = std::fs::read_dir(src_path.join("bin")) {
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_dir() {
+                    roots.push(path.join("main.rs"));
+                } else if is_rust_file(&path) {
+                    roots.push(path);
+                }
+            }
+        }
+    }
+
+    roots
+        .into_iter()
+        .filter(|root| root.is_file())
+        .map(|root| normalize_path(&root))
+        .collect()
+}
+
+// Retrieves all Rust files inside the directory of a crate, excluding
+// the build artifacts and the directories of other crates.
+fn get_crate_rust_files(crate_path: &Path) -> Vec<PathBuf> {
+    let mut rust_files = Vec::new();
+    let mut stack = vec![crate_path.to_path_buf()];
+
+    while let Some(current_dir) = stack.pop() {
+        if let Ok(entries) = std::fs::read_dir(current_dir) {
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_dir() {
+                    if path.file_name().and_then(|name| name.to_str()) != Some(TARGET_DIRECTORY)
+                        && !path.join("Cargo.toml").is_file()
+                    {
+                        stack.push(path);
+                    }
+                } else if is_rust_file(&path) {
+                    rust_files.push(normalize_path(&path));
+                }
+            }
+        }
+    }
+
+    rust_files
+}
+
+// Retrieves the files of the crate whose manifest is at `cargo_toml_path`,
+// following the `mod` declarations of its lib and bin targets.
+//
+// Rust files of the crate which are not part of the module tree of any target,
+// such as build scripts or dead files, are reported as unreachable.
+fn get_crate_files(cargo_toml_path: &Path) -> Result<FirmwareFiles> {
+    let cargo_toml: Value = std::fs::read_to_string(cargo_toml_path)?.parse()?;
+    let crate_path = cargo_toml_path.parent().unwrap_or(Path::new("."));
+
+    let roots = get_crate_roots(&cargo_toml, crate_path);
+    if roots.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Analysis,
+            "The firmware crate does not define any lib or bin target",
+        ));
+    }
+
+    // Visit the module tree of each target.
+    let mut visited: HashSet<PathBuf> = roots.iter().cloned().collect();
+    let mut stack: Vec<_> = roots
+        .into_iter()
+        .map(|root| (root, true, Vec::new()))
+        .collect();
+    let mut files = Vec::new();
+
+    while let Some((path, is_crate_root, cfg)) = stack.pop() {
+        let fw_file = FirmwareFile::new(path.clone().into(), cfg)?;
+        let source_code = &fw_file.source_code;
+
+        for submodule in get_submodules(&path, is_crate_root, fw_file.root(), source_code) {
+            // Skip the modules whose file is missing.
+            if submodule.path.is_file() && visited.insert(submodule.path.clone()) {
+                // The submodule inherits the cfg attributes of its `mod` declaration.
+                let cfg = fw_file
+                    .cfg
+                    .iter()
+                    .cloned()
+                    .chain(
+                        cfg_predicates(submodule.item, source_code)
+                            .into_iter()
+                            .map(String::from),
+                    )
+                    .collect();
+                stack.push((submodule.path, false, cfg));
+            }
+        }
+
+        files.push(fw_file);
+    }
+
+    let unreachable_files = get_crate_rust_files(crate_path)
+        .into_iter()
+        .filter(|path| !visited.contains(path))
+        .collect();
+
+    Ok(FirmwareFiles {
+        files,
+        unreachable_files,
+    })
+}
+
+// Member of a Cargo workspace.
+pub(crate) struct WorkspaceMember {
+    // Package name.
+    pub(crate) name: String,
+    // Path to the `Cargo.toml` manifest of the package.
+    pub(crate) manifest_path: PathBuf,
+}
+
+// Returns `true` if `name` matches `pattern`, which may contain
+// the `*` and `?` wildcards.
+fn matches_wildcard(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            matches_wildcard(rest, name)
+                || (!name.is_empty() && matches_wildcard(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name_rest))) => matches_wildcard(rest, name_rest),
+        (Some((first, rest)), Some((name_first, name_rest))) => {
+            first == name_first && matches_wildcard(rest, name_rest)
+        }
+        _ => false,
+    }
+}
+
+// Expands a workspace member pattern, such as `firmwares/*`,
+// into the directories it matches.
+fn expand_member(workspace_path: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut directories = vec![workspace_path.to_path_buf()];
+
+    for segment in pattern.split('/').filter(|segment| !segment.is_empty()) {
+        if !segment.contains(['*', '?']) {
+            directories = directories
+                .into_iter()
+                .map(|directory| directory.join(segment))
+                .collect();
+            continue;
+        }
+
+        directories = directories
+            .into_iter()
+            .filter_map(|directory| std::fs::read_dir(directory).ok())
+            .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_dir()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .map_or(false, |name| {
+                            matches_wildcard(segment.as_bytes(), name.as_bytes())
+                        })
+            })
+            .collect();
+    }
+
+    directories
+}
+
+#[inline(always)]
+// Retrieves the package name declared inside a `Cargo.toml` manifest.
+fn package_name(cargo_toml: &Value) -> Option<&str> {
+    cargo_toml
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+}
+
+// Retrieves the members of the workspace whose manifest is at `fw_path`.
+//
+// Returns `None` if `fw_path` is not the manifest of a workspace.
+// A workspace manifest which also declares a package counts as a member.
+pub(crate) fn get_workspace_members(fw_path: &Path) -> Result<Option<Vec<WorkspaceMember>>> {
+    if !is_cargo_manifest(fw_path) {
+        return Ok(None);
+    }
+
+    let cargo_toml: Value = std::fs::read_to_string(fw_path)?.parse()?;
+    let Some(workspace) = cargo_toml.get("workspace") else {
+        return Ok(None);
+    };
+    let workspace_path = fw_path.parent().unwrap_or(Path::new("."));
+
+    // Get the paths of a list of the workspace table.
+    let get_paths = |key: &str| -> Vec<&str> {
+        workspace
+            .get(key)
+            .and_then(|paths| paths.as_array())
+            .map_or(Vec::new(), |paths| {
+                paths.iter().filter_map(|path| path.as_str()).collect()
+            })
+    };
+
+    let excluded: HashSet<_> = get_paths("exclude")
+        .into_iter()
+        .map(|path| normalize_path(&workspace_path.join(path)))
+        .collect();
+
+    let mut members = Vec::new();
+    if let Some(name) = package_name(&cargo_toml) {
+        members.push(WorkspaceMember {
+            name: name.to_string(),
+            manifest_path: fw_path.to_path_buf(),
+        });
+    }
+
+    for pattern in get_paths("members") {
+        for directory in expand_member(workspace_path, pattern) {
+            let directory = normalize_path(&directory);
+            let manifest_path = directory.join("Cargo.toml");
+            if excluded.contains(&directory) || !manifest_path.is_file() {
+                continue;
+            }
+
+            let member_toml: Value = std::fs::read_to_string(&manifest_path)?.parse()?;
+            if let Some(name) = package_name(&member_toml) {
+                members.push(WorkspaceMember {
+                    name: name.to_string(),
+                    manifest_path,
+                });
+            }
+        }
+    }
+
+    Ok(Some(members))
+}
+
+pub(crate) fn get_fw_files(fw_path: &Path) -> Result<FirmwareFiles> {
+    // `firmware_path` is the manifest of a crate.
+    if is_cargo_manifest(fw_path) {
+        return get_crate_files(fw_path);
+    }
+
     // `firmware_path` is a single file.
     if fw_path.is_file() && is_rust_file(fw_path) {
-        let fw_file = FirmwareFile::new(fw_path.into())?;
-        return Ok(vec![fw_file]);
+        let fw_file = FirmwareFile::new(fw_path.into(), Vec::new())?;
+        return Ok(FirmwareFiles {
+            files: vec![fw_file],
+            unreachable_files: Vec::new(),
+        });
     }
 
     // `firmware_path` is a directory, so we have to retrieve
@@ -56,12 +371,15 @@ pub(crate) fn get_fw_files(fw_path: &Path) -> Result<Vec<FirmwareFile>> {
                 if path.is_dir() {
                     stack.push(path.to_path_buf());
                 } else if is_rust_file(&path) {
-                    let fw_file = FirmwareFile::new(path.into())?;
+                    let fw_file = FirmwareFile::new(path.into(), Vec::new())?;
                     fw_files.push(fw_file);
                 }
             }
         }
     }
 
-    Ok(fw_files)
+    Ok(FirmwareFiles {
+        files: fw_files,
+        unreachable_files: Vec::new(),
+    })
 }
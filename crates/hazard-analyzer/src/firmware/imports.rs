@@ -0,0 +1,152 @@
+// This module defines functions
+// for parsing the `use` declarations
+// of a firmware file.
+
+use std::collections::HashMap;
+
+use rust_code_analysis::{Node, Rust, Search};
+
+use crate::modules::last_segment;
+
+// Imports of a firmware file.
+#[derive(Debug, Default)]
+pub(crate) struct Imports {
+    // Names introduced by the imports, mapped to their full paths.
+    //
+    // `use ascot_axum::devices::light::Light as Lamp;`
+    // maps `Lamp` to `ascot_axum::devices::light::Light`.
+    names: HashMap<String, String>,
+    // Paths imported through a glob.
+    //
+    // `use ascot_axum::devices::light::*;`
+    // adds `ascot_axum::devices::light`.
+    globs: Vec<String>,
+}
+
+#[inline(always)]
+// Joins a path prefix and a path, removing whitespaces from the latter.
+fn join_path(prefix: &str, path: &str) -> String {
+    let path = path.split_whitespace().collect::<String>();
+    if prefix.is_empty() {
+        path
+    } else {
+        format!("{prefix}::{path}")
+    }
+}
+
+#[inline(always)]
+// Returns `true` if the node is a token or a comment of a use tree.
+fn is_use_token(node: &Node, source_code: &[u8]) -> bool {
+    matches!(
+        Rust::from(node.kind_id()),
+        Rust::LineComment | Rust::BlockComment | Rust::VisibilityModifier
+    ) || matches!(
+        node.utf8_text(source_code),
+        Some("use" | "::" | "{" | "}" | "," | ";" | "as")
+    )
+}
+
+impl Imports {
+    // Retrieves the imports of a file starting from its root.
+    pub(crate) fn new(root: Node, source_code: &[u8]) -> Self {
+        let mut imports = Self::default();
+
+        for use_declaration in root.all_occurrences(|n| n.kind_id() == Rust::UseDeclaration) {
+            if let Some(argument) = use_declaration
+                .children()
+                .filter(|child| !is_use_token(child, source_code))
+                .last()
+            {
+                imports.add_use_tree(argument, "", source_code);
+            }
+        }
+
+        imports
+    }
+
+    // Adds the imports of a use tree, whose paths are preceded by `prefix`.
+    fn add_use_tree(&mut self, node: Node, prefix: &str, source_code: &[u8]) {
+        match Rust::from(node.kind_id()) {
+            // `path as alias`.
+            Rust::UseAsClause => {
+                let mut children = node
+                    .children()
+                    .filter(|child| !is_use_token(child, source_code));
+                if let (Some(path), Some(alias)) = (
+                    children.next().and_then(|path| path.utf8_text(source_code)),
+                    children
+                        .next()
+                        .and_then(|alias| alias.utf8_text(source_code)),
+                ) {
+                    self.names
+                        .insert(alias.to_string(), join_path(prefix, path));
+                }
+            }
+            // `path::{..}`.
+            Rust::ScopedUseList => {
+                let parts = node
+                    .children()
+                    .filter(|child| !is_use_token(child, source_code))
+                    .collect::<Vec<_>>();
+                if let Some((list, path)) = parts.split_last() {
+                    let prefix = match path.first().and_then(|path| path.utf8_text(source_code)) {
+                        Some(path) => join_path(prefix, path),
+                        None => prefix.to_string(),
+                    };
+                    self.add_use_tree(*list, &prefix, source_code);
+                }
+            }
+            // `{..}`.
+            Rust::UseList => {
+                for child in node
+                    .children()
+                    .filter(|child| !is_use_token(child, source_code))
+                {
+                    self.add_use_tree(child, prefix, source_code);
+                }
+            }
+            // `path::*`.
+            Rust::UseWildcard => {
+                if let Some(path) = node.utf8_text(source_code) {
+                    let path = path.trim_end_matches('*').trim_end().trim_end_matches("::");
+                    self.globs.push(join_path(prefix, path));
+                }
+            }
+            // `path`, `self` included.
+            _ => {
+                if let Some(path) = node.utf8_text(source_code) {
+                    let path = if path == "self" {
+                        prefix.to_string()
+                    } else {
+                        join_path(prefix, path)
+                    };
+                    self.names.insert(last_segment(&path).to_string(), path);
+                }
+            }
+        }
+    }
+
+    // Resolves a path used inside the file to its full path by expanding
+    // its first segment through the imports.
+    //
+    // Returns `None` if the first segment has not been imported.
+    pub(crate) fn resolve(&self, path: &str) -> Option<String> {
+        let path = path.split_whitespace().collect::<String>();
+        let (first, rest) = match path.split_once("::") {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (path.as_str(), None),
+        };
+
+        let imported = self.names.get(first)?;
+        Some(match rest {
+            Some(rest) => format!("{imported}::{rest}"),
+            None => imported.clone(),
+        })
+    }
+
+    // Returns the paths imported through a glob.
+    #[inline(always)]
+    pub(crate) fn globs(&self) -> &[String] {
+        &self.globs
+    }
+}
@@ -1,8 +1,17 @@
+mod body;
+pub(crate) mod cfg;
+mod chain;
+mod diagnostics;
 pub(crate) mod file;
+mod imports;
+mod resolve;
+mod route;
+mod server;
+pub(crate) mod symbols;
 
 use std::{
-    collections::{HashMap, HashSet},
-    path::Path,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
 };
 
 use code_certifier::error::Result;
@@ -13,9 +22,75 @@ use serde::Serialize;
 
 use crate::{
     concurrent::ConcurrentRunner,
-    device::{AscotDevice, DeviceAction, MandatoryActions},
-    re::{method_re, ARGS_RE, HAZARD_RE},
+    device::{
+        hazards::{HazardCatalog, HazardDescription, UnknownDeviceHazards, UnknownHazard},
+        AscotDevice, DeviceAction, DeviceFileReport, HazardMismatch, MandatoryActions,
+    },
+    modules::last_segment,
+    policy::Policy,
+    re::hazards_from_text,
 };
+use body::analyze_handler;
+pub(crate) use body::{FindingKind, HandlerFinding};
+use cfg::{is_enabled, CfgSet};
+use chain::{chain_top, get_arguments, instance_chain, MethodCall, MethodChain};
+use diagnostics::{get_diagnostics, get_input_violations, get_method_violations};
+pub(crate) use diagnostics::{
+    ActionLocation, Diagnostic, InputViolation, InputViolationKind, MethodViolation,
+};
+use resolve::{resolve_binding, returned_expression};
+use route::get_route;
+pub(crate) use route::Route;
+use server::get_device_status;
+pub(crate) use server::DeviceStatus;
+use symbols::{FunctionItem, Symbols};
+
+// Crate defining the Ascot devices.
+const ASCOT_DEVICES_CRATE: &str = "ascot_axum";
+
+// Helper function through which an action has been resolved.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HelperFunction<'a> {
+    // Function name.
+    pub(crate) name: &'a str,
+    // File in which the function is declared.
+    pub(crate) file: &'a Path,
+    // Row and column of the function inside the file.
+    pub(crate) position: (usize, usize),
+}
+
+// Issue of an action handler.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum HandlerIssue {
+    // The handler is not declared inside the crate.
+    Missing,
+    // The handler is not an `async` function.
+    NotAsync,
+    // The handler is shared with actions carrying different hazards.
+    SharedWithDifferentHazards,
+}
+
+// Handler of an action.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Handler<'a> {
+    // Handler name, as written in the DeviceAction.
+    pub(crate) name: &'a str,
+    // File in which the handler function is declared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) file: Option<&'a Path>,
+    // Row and column of the handler function inside the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) position: Option<(usize, usize)>,
+    // Issues of the handler.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) issues: Vec<HandlerIssue>,
+    // Constructs found inside the handler body, if handler bodies are analyzed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) findings: Vec<HandlerFinding<'a>>,
+}
 
 // MandatoryAction.
 #[derive(Debug, Serialize)]
@@ -23,6 +98,15 @@ use crate::{
 pub(crate) struct MandatoryAction<'a> {
     // Action name.
     pub(crate) name: &'a str,
+    // Row and column of the action inside the firmware file, that is the
+    // `DeviceAction` argument of `new()` or the method named after the action.
+    pub(crate) position: (usize, usize),
+    // Route configuration of the action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) route: Option<Route<'a>>,
+    // Action handler.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) handler: Option<Handler<'a>>,
     // Action hazards.
     pub(crate) hazards: HashSet<&'a str>,
     // Mandatory hazards that should be set for this action.
@@ -33,6 +117,12 @@ pub(crate) struct MandatoryAction<'a> {
     // Hazards that are not allowed for the device.
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     pub(crate) not_allowed_hazards: HashSet<String>,
+    // Hazards that are not variants of the `Hazard` enum.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) unknown_hazards: Vec<UnknownHazard<'a>>,
+    // Helper functions through which the action has been resolved, in call order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) resolved_through: Vec<HelperFunction<'a>>,
 }
 
 // OptionalAction.
@@ -41,19 +131,72 @@ pub(crate) struct MandatoryAction<'a> {
 pub(crate) struct OptionalAction<'a> {
     // Action name.
     pub(crate) name: &'a str,
+    // Row and column of the action inside the firmware file.
+    pub(crate) position: (usize, usize),
+    // Route configuration of the action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) route: Option<Route<'a>>,
+    // Action handler.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) handler: Option<Handler<'a>>,
     // Action hazards.
     pub(crate) hazards: HashSet<&'a str>,
     // Hazards that are not allowed for the device.
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     pub(crate) not_allowed_hazards: HashSet<String>,
+    // Hazards that are not variants of the `Hazard` enum.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) unknown_hazards: Vec<UnknownHazard<'a>>,
+    // Helper functions through which the action has been resolved, in call order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) resolved_through: Vec<HelperFunction<'a>>,
 }
 
 // Instance of a device in the firmware.
 struct DeviceInstance<'a> {
-    // Code.
-    code: &'a str,
+    // Method chain of the instance.
+    chain: MethodChain<'a>,
     // Row and column of the instance inside the firmware file.
     position: (usize, usize),
+    // Whether the instance is built and served.
+    status: DeviceStatus,
+}
+
+// Context of the analysis of a firmware file.
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    // File under analysis.
+    firmware_file: &'a FirmwareFile<'a>,
+    // Symbol table of the firmware crate.
+    symbols: &'a Symbols<'a>,
+    // Maximum number of nested helper functions followed while resolving an action.
+    helper_depth: usize,
+    // Active features and cfg options, if cfg attributes are evaluated.
+    cfg: Option<&'a CfgSet>,
+    // Certification policy.
+    policy: &'a Policy,
+    // Maximum number of nested calls followed while analyzing handler bodies,
+    // if handler bodies are analyzed.
+    handler_body_depth: Option<usize>,
+    // Catalog of the valid hazards, if hazard names are checked.
+    hazard_catalog: Option<&'a HazardCatalog>,
+}
+
+impl<'a> Context<'a> {
+    #[inline(always)]
+    fn source_code(&self) -> &'a [u8] {
+        &self.firmware_file.source_code
+    }
+}
+
+// DeviceAction expression an action argument resolves to.
+struct ResolvedAction<'a> {
+    // Resolved expression, usually a `DeviceAction::..()` call.
+    expression: Node<'a>,
+    // Context of the file containing the expression.
+    context: Context<'a>,
+    // Helper functions followed to reach the expression, in call order.
+    helpers: Vec<HelperFunction<'a>>,
 }
 
 // Device.
@@ -67,6 +210,8 @@ pub(crate) struct Device<'a> {
     pub(crate) name: &'a str,
     // Row and column of the device instance inside the firmware file.
     pub(crate) position: (usize, usize),
+    // Whether the device instance is built and served.
+    pub(crate) status: DeviceStatus,
     // Defined mandatory actions.
     pub(crate) mandatory_actions: Vec<MandatoryAction<'a>>,
     // Mandatory actions that have not been defined.
@@ -77,6 +222,18 @@ pub(crate) struct Device<'a> {
     pub(crate) optional_actions: Vec<OptionalAction<'a>>,
     // Allowed hazards for this device.
     pub(crate) allowed_hazards: &'a HashSet<&'a str>,
+    // Hazards of the device actions grouped by category.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) hazard_categories: BTreeMap<&'a str, BTreeSet<&'a str>>,
+    // Actions exposing hazards through HTTP methods not allowed by the policy.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) method_violations: Vec<MethodViolation<'a>>,
+    // Route inputs of the actions which violate the policy.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) input_violations: Vec<InputViolation<'a>>,
+    // Issues found among the device actions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) diagnostics: Vec<Diagnostic<'a>>,
 }
 
 // FileManifest.
@@ -89,19 +246,424 @@ pub(crate) struct FileManifest<'a> {
     pub(crate) devices: Vec<Device<'a>>,
 }
 
-// Returns all the hazards containd in a piece of code.
-// It will for example retrieve "FireHazard" and "PowerSurge" from:
+// Manifest.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Manifest<'a> {
+    // Files instantiating at least one device.
+    pub(crate) files: Vec<FileManifest<'a>>,
+    // Files of the firmware crate which are not reachable from its targets.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) unreachable_files: &'a [PathBuf],
+    // Results of the analysis with each combination of features.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) feature_combinations: Vec<FeatureCombination<'a>>,
+    // Descriptions of the hazards referenced by the devices, sorted by name.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) hazards: Vec<HazardDescription<'a>>,
+    // Unknown hazards used inside the definitions of the Ascot devices.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) unknown_device_hazards: &'a [UnknownDeviceHazards<'a>],
+    // Mandatory actions whose checked hazards differ from the hazards
+    // of the const named after them inside the definitions of the Ascot devices.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) hazard_mismatches: &'a [HazardMismatch<'a>],
+    // Reports of the device files, describing how their devices have been understood.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) device_catalog: &'a [DeviceFileReport<'a>],
+}
+
+// Device instance.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeviceInstanceLocation<'a> {
+    // File containing the device instance.
+    pub(crate) file: &'a Path,
+    // Device name.
+    pub(crate) name: &'a str,
+    // Row and column of the device instance inside the file.
+    pub(crate) position: (usize, usize),
+}
+
+// FeatureCombination.
 //
-// add_action(DeviceAction::with_hazards(toggle_config, toggle, &[Hazard::FireHazard, Hazard::PowerSurge]))
-fn get_hazards(code: &str) -> Option<HashSet<&str>> {
-    let hazards = HAZARD_RE
-        .captures_iter(code)
-        .filter_map(|capture| capture.get(1).map(|m| m.as_str()))
-        .collect::<HashSet<_>>();
+// Result of the analysis of a firmware with a combination of active features.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FeatureCombination<'a> {
+    // Active features, sorted by name.
+    pub(crate) features: Vec<&'a str>,
+    // Whether all the devices are Ascot compliant with these features.
+    pub(crate) compliant: bool,
+    // Devices which are not compliant with these features.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) non_compliant_devices: Vec<DeviceInstanceLocation<'a>>,
+}
+
+impl<'a> FeatureCombination<'a> {
+    // Creates the result of the analysis with `features` starting from its file manifests.
+    pub(crate) fn new(features: &[&'a str], files: &[FileManifest<'a>]) -> Self {
+        let mut non_compliant_devices: Vec<_> = files
+            .iter()
+            .flat_map(|file| {
+                file.devices
+                    .iter()
+                    .filter(|device| !device.is_compliant())
+                    .map(|device| DeviceInstanceLocation {
+                        file: file.file,
+                        name: device.name,
+                        position: device.position,
+                    })
+            })
+            .collect();
+        non_compliant_devices.sort_by_key(|device| (device.file, device.position));
+
+        Self {
+            features: features.to_vec(),
+            compliant: non_compliant_devices.is_empty(),
+            non_compliant_devices,
+        }
+    }
+}
+
+// PackageManifest.
+//
+// Manifest of a package of a workspace.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PackageManifest<'a> {
+    // Package name.
+    pub(crate) name: &'a str,
+    // Whether all the devices of the package are Ascot compliant.
+    pub(crate) compliant: bool,
+    // Package manifest.
+    #[serde(flatten)]
+    pub(crate) manifest: Manifest<'a>,
+}
+
+// WorkspaceManifest.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceManifest<'a> {
+    // Manifests of the workspace packages, sorted by name.
+    pub(crate) packages: Vec<PackageManifest<'a>>,
+    // Unknown hazards used inside the definitions of the Ascot devices.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) unknown_device_hazards: &'a [UnknownDeviceHazards<'a>],
+    // Mandatory actions whose checked hazards differ from the hazards
+    // of the const named after them inside the definitions of the Ascot devices.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) hazard_mismatches: &'a [HazardMismatch<'a>],
+    // Reports of the device files, describing how their devices have been understood.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub(crate) device_catalog: &'a [DeviceFileReport<'a>],
+}
+
+impl<'a> Device<'a> {
+    // Returns `true` if the device is built and served, and it defines all mandatory
+    // actions and hazards, without using hazards that are unknown or not allowed,
+    // or violating the policy.
+    pub(crate) fn is_compliant(&self) -> bool {
+        self.status.is_served()
+            && self
+                .missing_mandatory_actions
+                .as_ref()
+                .map_or(true, |missing| missing.is_empty())
+            && self.mandatory_actions.iter().all(|action| {
+                action.missing_hazards.is_empty()
+                    && action.not_allowed_hazards.is_empty()
+                    && action.unknown_hazards.is_empty()
+            })
+            && self.optional_actions.iter().all(|action| {
+                action.not_allowed_hazards.is_empty() && action.unknown_hazards.is_empty()
+            })
+            && self.method_violations.is_empty()
+            && self.input_violations.is_empty()
+    }
+
+    // Returns the hazards referenced by the device, either by its actions,
+    // as mandatory hazards or as allowed hazards.
+    pub(crate) fn referenced_hazards(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.mandatory_actions
+            .iter()
+            .flat_map(|action| action.hazards.iter().chain(action.mandatory_hazards))
+            .chain(
+                self.optional_actions
+                    .iter()
+                    .flat_map(|action| &action.hazards),
+            )
+            .chain(self.allowed_hazards)
+            .copied()
+    }
+}
+
+impl<'a> Manifest<'a> {
+    #[inline(always)]
+    // Returns `true` if all the devices of the manifest are compliant,
+    // also with each analyzed combination of features.
+    pub(crate) fn is_compliant(&self) -> bool {
+        self.files
+            .iter()
+            .flat_map(|file| &file.devices)
+            .all(Device::is_compliant)
+            && self
+                .feature_combinations
+                .iter()
+                .all(|combination| combination.compliant)
+    }
+}
+
+// Retrieves the hazards passed to `DeviceAction` through an argument,
+// that is either a list of hazards or a reference to a const or static item.
+fn get_argument_hazards<'a>(
+    hazards_argument: Node<'a>,
+    context: Context<'a>,
+) -> Option<HashSet<&'a str>> {
+    let source_code = context.source_code();
+    let mut hazards_value = resolve_binding(hazards_argument, source_code, context.cfg);
+
+    // Remove the reference from `&HAZARDS`.
+    if hazards_value.kind_id() == Rust::ReferenceExpression {
+        hazards_value = hazards_value.child(hazards_value.child_count() - 1)?;
+    }
+
+    let hazards = hazards_from_text(hazards_value.utf8_text(source_code)?)?;
+    if !hazards.is_empty()
+        || !matches!(
+            Rust::from(hazards_value.kind_id()),
+            Rust::Identifier | Rust::ScopedIdentifier
+        )
+    {
+        return Some(hazards);
+    }
+
+    // Search the hazards in the const and static items of the crate.
+    let hazards = context
+        .symbols
+        .hazards(
+            &context.firmware_file.path,
+            hazards_value.utf8_text(source_code)?,
+            context.cfg,
+        )
+        .map_or_else(HashSet::new, |hazards| hazards.iter().copied().collect());
 
     Some(hazards)
 }
 
+// Retrieves the function of the crate called by `expression`, such as
+// `light_on_action()` or `actions::light_on_action()`.
+fn get_helper_call<'a>(expression: Node<'a>, context: Context<'a>) -> Option<&'a FunctionItem<'a>> {
+    if expression.kind_id() != Rust::CallExpression {
+        return None;
+    }
+
+    let function = expression.child(0).filter(|function| {
+        matches!(
+            Rust::from(function.kind_id()),
+            Rust::Identifier | Rust::ScopedIdentifier
+        )
+    })?;
+
+    context.symbols.function(
+        &context.firmware_file.path,
+        function.utf8_text(context.source_code())?,
+        context.cfg,
+    )
+}
+
+// Resolves an action argument to the expression building the `DeviceAction`.
+//
+// The argument is resolved through the `let` declarations of the enclosing
+// function and, when it is a call to a helper function of the crate, such as
+// `fn light_on_action() -> DeviceAction<..> { DeviceAction::with_hazard(..) }`,
+// through the expression returned by the helper, up to `helper_depth` nested helpers.
+fn resolve_action<'a>(argument: Node<'a>, context: Context<'a>) -> ResolvedAction<'a> {
+    let mut context = context;
+    let mut expression = resolve_binding(argument, context.source_code(), context.cfg);
+    let mut helpers = Vec::new();
+
+    while helpers.len() < context.helper_depth {
+        let Some(helper) = get_helper_call(expression, context) else {
+            break;
+        };
+        let Some(returned) = helper
+            .node()
+            .and_then(|function| returned_expression(function, &helper.firmware_file.source_code))
+        else {
+            break;
+        };
+
+        // Continue the analysis inside the file of the helper.
+        context = Context {
+            firmware_file: helper.firmware_file,
+            ..context
+        };
+        expression = resolve_binding(returned, context.source_code(), context.cfg);
+        helpers.push(HelperFunction {
+            name: helper.name,
+            file: &helper.firmware_file.path,
+            position: helper.position,
+        });
+    }
+
+    ResolvedAction {
+        expression,
+        context,
+        helpers,
+    }
+}
+
+// Retrieves the hazards of a resolved action, such as
+// `DeviceAction::with_hazard(config, handler, Hazard::FireHazard)`.
+//
+// The hazards passed to `DeviceAction` are resolved through the `let`
+// declarations of the enclosing function and the const and static items of the crate.
+fn get_action_hazards<'a>(action: &ResolvedAction<'a>) -> Option<HashSet<&'a str>> {
+    let source_code = action.context.source_code();
+    let device_action = action.expression;
+    let arguments = get_arguments(device_action);
+
+    // The expression is not a DeviceAction::..() call, so search hazards in its whole code.
+    if device_action.kind_id() != Rust::CallExpression || arguments.len() < 2 {
+        return hazards_from_text(device_action.utf8_text(source_code)?);
+    }
+
+    // Hazards are passed starting from the third argument.
+    let mut hazards = HashSet::new();
+    for hazards_argument in arguments.into_iter().skip(2) {
+        hazards.extend(get_argument_hazards(hazards_argument, action.context)?);
+    }
+
+    Some(hazards)
+}
+
+// Retrieves the route configuration of a resolved action, which is
+// the first argument of DeviceAction::..().
+fn get_action_route<'a>(action: &ResolvedAction<'a>) -> Option<Route<'a>> {
+    let config = get_arguments(action.expression).into_iter().next()?;
+    get_route(config, action.context.source_code(), action.context.cfg)
+}
+
+// Retrieves the handler name of a resolved action, which is
+// the second (nth(1)) argument of DeviceAction::..().
+fn get_action_handler<'a>(action: &ResolvedAction<'a>) -> Option<&'a str> {
+    get_arguments(action.expression)
+        .get(1)?
+        .utf8_text(action.context.source_code())
+}
+
+// Retrieves the handler of a resolved action, resolving its name
+// to the function item of the crate.
+fn get_handler<'a>(action: &ResolvedAction<'a>) -> Option<Handler<'a>> {
+    let context = action.context;
+    let argument = *get_arguments(action.expression).get(1)?;
+    let name = argument.utf8_text(context.source_code())?;
+
+    // Only paths can be resolved, closures are not.
+    let is_path = matches!(
+        Rust::from(argument.kind_id()),
+        Rust::Identifier | Rust::ScopedIdentifier
+    );
+    let function = is_path
+        .then(|| {
+            context
+                .symbols
+                .function(&context.firmware_file.path, name, context.cfg)
+        })
+        .flatten();
+
+    let mut issues = Vec::new();
+    match function {
+        Some(function) if !function.is_async => issues.push(HandlerIssue::NotAsync),
+        // A name declared more than once is ambiguous, not missing.
+        None if is_path && !context.symbols.has_function(name) => {
+            issues.push(HandlerIssue::Missing)
+        }
+        _ => {}
+    }
+
+    let findings = function
+        .zip(context.handler_body_depth)
+        .map(|(function, depth)| analyze_handler(function, context, depth))
+        .unwrap_or_default();
+
+    Some(Handler {
+        name,
+        file: function.map(|function| function.firmware_file.path.as_ref()),
+        position: function.map(|function| function.position),
+        issues,
+        findings,
+    })
+}
+
+// Flags the handlers shared between actions carrying different hazards.
+fn check_shared_handlers(
+    mandatory_actions: &mut [MandatoryAction],
+    optional_actions: &mut [OptionalAction],
+) {
+    // Group the hazards of the actions by handler.
+    let mut handlers_hazards = HashMap::<_, Vec<_>>::new();
+    for (handler, hazards) in mandatory_actions
+        .iter()
+        .map(|action| (&action.handler, &action.hazards))
+        .chain(
+            optional_actions
+                .iter()
+                .map(|action| (&action.handler, &action.hazards)),
+        )
+    {
+        if let Some(handler) = handler {
+            handlers_hazards
+                .entry(handler.name)
+                .or_default()
+                .push(hazards.clone());
+        }
+    }
+
+    let shared: HashSet<_> = handlers_hazards
+        .into_iter()
+        .filter(|(_, hazards)| hazards.iter().any(|other| *other != hazards[0]))
+        .map(|(name, _)| name)
+        .collect();
+
+    for handler in mandatory_actions
+        .iter_mut()
+        .map(|action| &mut action.handler)
+        .chain(
+            optional_actions
+                .iter_mut()
+                .map(|action| &mut action.handler),
+        )
+        .flatten()
+        .filter(|handler| shared.contains(handler.name))
+    {
+        handler
+            .issues
+            .push(HandlerIssue::SharedWithDifferentHazards);
+    }
+}
+
+// Retrieves the hazards of an action which are not variants of the `Hazard` enum
+// and the ones which are not allowed for the device, excluding the unknown ones.
+fn check_hazards<'a>(
+    hazards: &HashSet<&'a str>,
+    allowed_hazards: &HashSet<&str>,
+    context: Context<'a>,
+) -> (Vec<UnknownHazard<'a>>, HashSet<String>) {
+    let Some(hazard_catalog) = context.hazard_catalog else {
+        return (Vec::new(), sets_difference(hazards, allowed_hazards));
+    };
+
+    let unknown_hazards = hazard_catalog.unknown_hazards(hazards.iter().copied());
+    let not_allowed_hazards = hazards
+        .difference(allowed_hazards)
+        .filter(|hazard| hazard_catalog.contains(hazard))
+        .map(|hazard| hazard.to_string())
+        .collect();
+
+    (unknown_hazards, not_allowed_hazards)
+}
+
 // Returns the difference between first_set and second_set.
 fn sets_difference(first_set: &HashSet<&str>, second_set: &HashSet<&str>) -> HashSet<String> {
     first_set
@@ -111,85 +673,85 @@ fn sets_difference(first_set: &HashSet<&str>, second_set: &HashSet<&str>) -> Has
 }
 
 fn get_optional_actions<'a>(
-    device_instance: &'a str,
+    device_instance: &DeviceInstance<'a>,
     ascot_device: &'a AscotDevice<'a>,
-) -> Option<Vec<OptionalAction<'a>>> {
-    // Get optional actions by searching all the add_action() calls of a device instance.
-    let optional_actions = method_re("add_action")?
-        .captures_iter(device_instance)
-        .filter_map(|captures| captures.get(1).map(|re_match| re_match.as_str()))
-        .filter_map(|add_action_args| {
+    context: Context<'a>,
+) -> Vec<OptionalAction<'a>> {
+    // Get optional actions from all the add_action() calls of a device instance.
+    device_instance
+        .chain
+        .calls_named("add_action")
+        .filter_map(|add_action| {
+            // Get the DeviceAction::..() argument of add_action().
+            let device_action = add_action.arguments.first()?;
+            let action = resolve_action(*device_action, context);
+
             // Get hazards.
-            let hazards = get_hazards(add_action_args)?;
+            let hazards = get_action_hazards(&action)?;
 
-            // Get optional action name by extracting the second (nth(1)) argument from add_action().
-            let name = crate::re::ARGS_RE
-                .captures(add_action_args)?
-                .get(1)?
-                .as_str()
-                .split(',')
-                .nth(1)
-                .map(|name| name.trim())?;
+            // Get optional action name from the DeviceAction::..() handler.
+            let name = get_action_handler(&action)?;
 
-            // Get eventual not allowed hazards.
-            let not_allowed_hazards = sets_difference(&hazards, &ascot_device.allowed_hazards);
+            // Get eventual unknown and not allowed hazards.
+            let (unknown_hazards, not_allowed_hazards) =
+                check_hazards(&hazards, &ascot_device.allowed_hazards, context);
 
             Some(OptionalAction {
                 name,
+                position: device_action.start_position(),
+                route: get_action_route(&action),
+                handler: get_handler(&action),
                 hazards,
                 not_allowed_hazards,
+                unknown_hazards,
+                resolved_through: action.helpers,
             })
         })
-        .collect();
-
-    Some(optional_actions)
-}
-
-// Retrieves the method call code that has name equal to the given `method_name`.
-fn get_method_call<'a>(device_instance: &'a str, method_name: &str) -> Option<&'a str> {
-    method_re(method_name)?
-        .captures(device_instance)
-        .and_then(|c| c.get(1))
-        .map(|c| c.as_str())
+        .collect()
 }
 
 // Get mandatory actions defined inside DeviceName::new() function call.
 fn get_new_actions<'a>(
     actions: &'a HashMap<usize, DeviceAction<'a>>,
-    new_call: &'a str,
+    new_call: &MethodCall<'a>,
     allowed_hazards: &'a HashSet<&'a str>,
-) -> Option<Vec<MandatoryAction<'a>>> {
+    context: Context<'a>,
+) -> Vec<MandatoryAction<'a>> {
     // Get all DeviceAction::..() arguments inside DeviceName::new() call.
-    let mandatory_actions = ARGS_RE
-        .captures_iter(new_call)
+    new_call
+        .arguments
+        .iter()
         .enumerate()
-        .filter_map(|(pos, capture)| {
-            let argument = capture.get(1)?.as_str();
-
+        .filter_map(|(pos, argument)| {
             // Get the action corresponding to the argument by
             // searching in the map the action which has argument number equal to `pos`.
             let action = actions.get(&pos)?;
 
             // Get hazards.
-            let hazards = get_hazards(argument)?;
+            let resolved = resolve_action(*argument, context);
+            let hazards = get_action_hazards(&resolved)?;
 
             // Get missing hazards.
             let missing_hazards = sets_difference(&action.hazards, &hazards);
 
-            // Get eventual not allowed hazards.
-            let not_allowed_hazards = sets_difference(&hazards, allowed_hazards);
+            // Get eventual unknown and not allowed hazards.
+            let (unknown_hazards, not_allowed_hazards) =
+                check_hazards(&hazards, allowed_hazards, context);
 
             Some(MandatoryAction {
                 name: &action.name,
+                position: argument.start_position(),
+                route: get_action_route(&resolved),
+                handler: get_handler(&resolved),
                 hazards,
                 mandatory_hazards: &action.hazards,
                 missing_hazards,
                 not_allowed_hazards,
+                unknown_hazards,
+                resolved_through: resolved.helpers,
             })
         })
-        .collect();
-
-    Some(mandatory_actions)
+        .collect()
 }
 
 // Get mandatory actions defined with methods calls.
@@ -197,49 +759,56 @@ fn get_methods_actions<'a>(
     actions: &[&'a DeviceAction],
     device_instance: &DeviceInstance<'a>,
     ascot_device: &AscotDevice,
-) -> Option<Vec<MandatoryAction<'a>>> {
+    context: Context<'a>,
+) -> Vec<MandatoryAction<'a>> {
     // Build mandatory actions objects starting from defined actions.
-    let mandatory_actions = actions
+    actions
         .iter()
         .filter_map(|action| {
-            // Get method call code to `.mandatory_action()`.
-            let action_method_call = get_method_call(device_instance.code, &action.name)?;
+            // Get the DeviceAction::..() argument of `.mandatory_action()`.
+            let call = device_instance.chain.call_named(&action.name)?;
+            let device_action = call.arguments.first()?;
 
             // Get hazards.
-            let hazards = get_hazards(action_method_call)?;
+            let resolved = resolve_action(*device_action, context);
+            let hazards = get_action_hazards(&resolved)?;
 
             // Get missing hazards.
             let missing_hazards = sets_difference(&action.hazards, &hazards);
 
-            // Get eventual not allowed hazards.
-            let not_allowed_hazards = sets_difference(&hazards, &ascot_device.allowed_hazards);
+            // Get eventual unknown and not allowed hazards.
+            let (unknown_hazards, not_allowed_hazards) =
+                check_hazards(&hazards, &ascot_device.allowed_hazards, context);
 
             Some(MandatoryAction {
                 name: &action.name,
+                position: call.position,
+                route: get_action_route(&resolved),
+                handler: get_handler(&resolved),
                 hazards,
                 mandatory_hazards: &action.hazards,
                 missing_hazards,
                 not_allowed_hazards,
+                unknown_hazards,
+                resolved_through: resolved.helpers,
             })
         })
-        .collect();
-
-    Some(mandatory_actions)
+        .collect()
 }
 
 fn build_device<'a>(
     device_instance: DeviceInstance<'a>,
     ascot_device: &'a AscotDevice<'a>,
+    context: Context<'a>,
 ) -> Option<Device<'a>> {
-    let (mandatory_actions, missing_mandatory_actions) = match &ascot_device.mandatory_actions {
+    let (mut mandatory_actions, missing_mandatory_actions) = match &ascot_device.mandatory_actions {
         MandatoryActions::New(actions) => {
-            // Get text of DeviceName::new() method call.
-            let new_call =
-                get_method_call(device_instance.code, &format!("{}::new", ascot_device.name))?;
+            // Get DeviceName::new() call.
+            let new_call = device_instance.chain.call_named("new")?;
 
-            // Get defined mandatory actions from new() method call.
+            // Get defined mandatory actions from new() arguments.
             let mandatory_actions =
-                get_new_actions(actions, new_call, &ascot_device.allowed_hazards)?;
+                get_new_actions(actions, new_call, &ascot_device.allowed_hazards, context);
 
             (mandatory_actions, None)
         }
@@ -248,10 +817,11 @@ fn build_device<'a>(
             // by checking if there is a method call where method name is equal to device action name.
             let (defined, missing): (Vec<_>, Vec<_>) = actions
                 .iter()
-                .partition(|action| get_method_call(device_instance.code, &action.name).is_some());
+                .partition(|action| device_instance.chain.call_named(&action.name).is_some());
 
             // Build mandatory actions objects starting from defined actions.
-            let mandatory_actions = get_methods_actions(&defined, &device_instance, ascot_device)?;
+            let mandatory_actions =
+                get_methods_actions(&defined, &device_instance, ascot_device, context);
 
             // Get missing mandatory actions names.
             let missing_mandatory_actions = missing
@@ -264,62 +834,191 @@ fn build_device<'a>(
     };
 
     // Get optional actions.
-    let optional_actions = get_optional_actions(device_instance.code, ascot_device)?;
+    let mut optional_actions = get_optional_actions(&device_instance, ascot_device, context);
+
+    // Check the handlers shared between actions.
+    check_shared_handlers(&mut mandatory_actions, &mut optional_actions);
+
+    // Get the method calls named after the mandatory actions of the device.
+    let mandatory_calls: Vec<_> = match &ascot_device.mandatory_actions {
+        MandatoryActions::New(_) => Vec::new(),
+        MandatoryActions::Methods(actions) => actions
+            .iter()
+            .flat_map(|action| device_instance.chain.calls_named(&action.name))
+            .collect(),
+    };
+
+    // Check route collisions and duplicate actions.
+    let diagnostics = get_diagnostics(&mandatory_actions, &optional_actions, &mandatory_calls);
+
+    // Check the HTTP methods exposing hazards.
+    let method_violations =
+        get_method_violations(&mandatory_actions, &optional_actions, context.policy);
+
+    // Check the route inputs of the actions.
+    let input_violations = get_input_violations(
+        ascot_device.name,
+        &mandatory_actions,
+        &optional_actions,
+        context.policy,
+    );
+
+    // Group the hazards of the actions by category.
+    let hazard_categories = context
+        .hazard_catalog
+        .map(|hazard_catalog| {
+            hazard_catalog.categorize(
+                mandatory_actions
+                    .iter()
+                    .flat_map(|action| &action.hazards)
+                    .chain(optional_actions.iter().flat_map(|action| &action.hazards))
+                    .copied(),
+            )
+        })
+        .unwrap_or_default();
 
     Some(Device {
         name: ascot_device.name,
         position: device_instance.position,
+        status: device_instance.status,
         mandatory_actions,
         missing_mandatory_actions,
         optional_actions,
         allowed_hazards: &ascot_device.allowed_hazards,
+        hazard_categories,
+        method_violations,
+        input_violations,
+        diagnostics,
+    })
+}
+
+#[inline(always)]
+// Returns `true` if `path` is a full path to the `device_name` type
+// of the Ascot devices crate, e.g. `ascot_axum::devices::light::Light`.
+fn is_ascot_device_path(path: &str, device_name: &str) -> bool {
+    path.trim_start_matches("::").split("::").next() == Some(ASCOT_DEVICES_CRATE)
+        && last_segment(path) == device_name
+}
+
+// Returns `true` if the file defines a struct, an enum or a type alias named `name`.
+fn defines_type(root: Node, name: &str, source_code: &[u8]) -> bool {
+    root.first_occurence(|n| {
+        matches!(
+            Rust::from(n.kind_id()),
+            Rust::StructItem | Rust::EnumItem | Rust::TypeItem
+        ) && n
+            .first_child(|child| child.kind_id() == Rust::TypeIdentifier)
+            .and_then(|identifier| identifier.utf8_text(source_code))
+            .map_or(false, |identifier| identifier == name)
     })
+    .is_some()
+}
+
+// Returns `true` if `type_path`, the path used in a `TypePath::new()` call,
+// refers to `ascot_device` once resolved through the file imports.
+fn is_ascot_device(
+    type_path: &str,
+    ascot_device: &AscotDevice,
+    root: Node,
+    context: Context,
+) -> bool {
+    let type_path = type_path.split_whitespace().collect::<String>();
+    let imports = context.symbols.imports(&context.firmware_file.path);
+
+    // The first segment of the path has been imported, e.g. `Lamp` for
+    // `use ascot_axum::devices::light::Light as Lamp;`.
+    if let Some(full_path) = imports.and_then(|imports| imports.resolve(&type_path)) {
+        return is_ascot_device_path(&full_path, ascot_device.name);
+    }
+
+    // A fully-qualified path.
+    if type_path.contains("::") {
+        return is_ascot_device_path(&type_path, ascot_device.name);
+    }
+
+    if type_path != ascot_device.name {
+        return false;
+    }
+
+    // The name has been imported through a glob of the Ascot devices crate.
+    let from_glob = imports.map_or(false, |imports| {
+        imports
+            .globs()
+            .iter()
+            .any(|glob| is_ascot_device_path(&format!("{glob}::{type_path}"), &type_path))
+    });
+
+    // Otherwise, the name must not be a type defined inside the file.
+    from_glob || !defines_type(root, &type_path, context.source_code())
 }
 
 fn get_device_instances<'a>(
     root: Node<'a>,
     ascot_device: &AscotDevice,
-    source_code: &'a [u8],
+    context: Context<'a>,
 ) -> Vec<DeviceInstance<'a>> {
-    // Get all nodes of type CallExpression to a DeviceName::new(), where DeviceName is
-    // ascot_device.name.
-    // To make sure that the node corresponds to the DeviceName::new() root node of the device instance,
-    // we have to ensure that it does not have other ancestors of type CallExpression.
-    let new_device_instances = root.all_occurrences(|n| {
+    let source_code = context.source_code();
+
+    // Get all nodes of type CallExpression to a TypePath::new(), where TypePath
+    // resolves to the ascot_device type, which are enabled by the cfg options.
+    let new_calls = root.all_occurrences(|n| {
         n.kind_id() == Rust::CallExpression
-            && n.utf8_text(source_code).map_or(false, |t| {
-                t.contains(&format!("{}::new", ascot_device.name))
-            })
-            && !n.has_ancestor(|a| a.kind_id() == Rust::CallExpression)
+            && n.child(0)
+                .filter(|function| function.kind_id() == Rust::ScopedIdentifier)
+                .and_then(|function| function.utf8_text(source_code))
+                .and_then(|function| function.strip_suffix("::new"))
+                .map_or(false, |type_path| {
+                    is_ascot_device(type_path, ascot_device, root, context)
+                })
+            && is_enabled(*n, context.cfg, source_code)
     });
 
-    // Create a DeviceInstance object with the device instance code, start row and start column.
-    new_device_instances
+    // Create a DeviceInstance object with the method chain starting from
+    // the TypePath::new() call, also when split across multiple statements,
+    // and the position of the instance.
+    new_calls
         .into_iter()
-        .filter_map(|n| {
-            let instance = n.utf8_text(source_code)?;
-            Some(DeviceInstance {
-                code: instance,
-                position: n.start_position(),
-            })
+        .map(|n| {
+            let top = chain_top(n);
+            let chain = instance_chain(top, source_code, context.cfg);
+            DeviceInstance {
+                status: get_device_status(top, &chain, context),
+                chain,
+                position: top.start_position(),
+            }
         })
         .collect()
 }
 
 fn get_file_manifest<'a>(
     firmware_file: &'a FirmwareFile,
-    ascot_devices: &'a [AscotDevice],
+    analyzer: &Analyzer<'a>,
 ) -> Option<FileManifest<'a>> {
+    // Skip the files whose `mod` declarations are disabled.
+    if !firmware_file.is_enabled(analyzer.cfg) {
+        return None;
+    }
+
     let root = firmware_file.root();
+    let context = Context {
+        firmware_file,
+        symbols: analyzer.symbols,
+        helper_depth: analyzer.helper_depth,
+        cfg: analyzer.cfg,
+        policy: analyzer.policy,
+        handler_body_depth: analyzer.handler_body_depth,
+        hazard_catalog: analyzer.hazard_catalog,
+    };
 
     // For each ascot device search in the file all the instances an build the
     // corresponding Device object.
-    let devices: Vec<_> = ascot_devices
+    let devices: Vec<_> = analyzer
+        .ascot_devices
         .iter()
         .flat_map(|ascot_device| {
-            get_device_instances(root, ascot_device, &firmware_file.source_code)
+            get_device_instances(root, ascot_device, context)
                 .into_iter()
-                .filter_map(|instance| build_device(instance, ascot_device))
+                .filter_map(move |instance| build_device(instance, ascot_device, context))
         })
         .collect();
 
@@ -330,11 +1029,43 @@ fn get_file_manifest<'a>(
     })
 }
 
-pub(crate) struct Analyzer<'a>(&'a [AscotDevice<'a>]);
+pub(crate) struct Analyzer<'a> {
+    // Ascot devices.
+    ascot_devices: &'a [AscotDevice<'a>],
+    // Symbol table of the firmware crate.
+    symbols: &'a Symbols<'a>,
+    // Maximum number of nested helper functions followed while resolving an action.
+    helper_depth: usize,
+    // Active features and cfg options, if cfg attributes are evaluated.
+    cfg: Option<&'a CfgSet>,
+    // Certification policy.
+    policy: &'a Policy,
+    // Maximum number of nested calls followed while analyzing handler bodies,
+    // if handler bodies are analyzed.
+    handler_body_depth: Option<usize>,
+    // Catalog of the valid hazards, if hazard names are checked.
+    hazard_catalog: Option<&'a HazardCatalog>,
+}
 
 impl<'a> Analyzer<'a> {
-    pub(crate) const fn new(ascot_devices: &'a [AscotDevice<'a>]) -> Self {
-        Self(ascot_devices)
+    pub(crate) const fn new(
+        ascot_devices: &'a [AscotDevice<'a>],
+        symbols: &'a Symbols<'a>,
+        helper_depth: usize,
+        cfg: Option<&'a CfgSet>,
+        policy: &'a Policy,
+        handler_body_depth: Option<usize>,
+        hazard_catalog: Option<&'a HazardCatalog>,
+    ) -> Self {
+        Self {
+            ascot_devices,
+            symbols,
+            helper_depth,
+            cfg,
+            policy,
+            handler_body_depth,
+            hazard_catalog,
+        }
     }
 }
 
@@ -362,7 +1093,7 @@ impl<'a> ConcurrentRunner<'a> for Analyzer<'a> {
         sender: Sender<Self::ConsumerItem>,
     ) -> Result<()> {
         while let Ok(firmware_file) = receiver.recv() {
-            if let Some(file_manifest) = get_file_manifest(firmware_file, self.0) {
+            if let Some(file_manifest) = get_file_manifest(firmware_file, self) {
                 sender.send(file_manifest)?;
             }
         }
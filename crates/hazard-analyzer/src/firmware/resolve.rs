@@ -0,0 +1,135 @@
+// This module defines functions
+// for resolving the identifiers of a firmware
+// to the values they are bound to.
+
+use rust_code_analysis::{Node, Rust, Search};
+
+use super::cfg::{is_enabled, CfgSet};
+
+// Maximum number of bindings followed while resolving an identifier,
+// e.g. `let first = ..; let second = first;`.
+const MAX_BINDINGS: usize = 8;
+
+#[inline(always)]
+// Returns `true` if the code of `outer` contains the code of `inner`.
+fn encloses(outer: &Node, inner: &Node) -> bool {
+    outer.start_byte() <= inner.start_byte() && inner.end_byte() <= outer.end_byte()
+}
+
+// Retrieves the function item enclosing `node`.
+pub(crate) fn enclosing_function(node: Node) -> Option<Node> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind_id() == Rust::FunctionItem {
+            return Some(parent);
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+// Retrieves the identifier and the value of a `let identifier = value;` declaration.
+pub(crate) fn let_binding<'a>(
+    let_declaration: Node<'a>,
+    source_code: &'a [u8],
+) -> Option<(&'a str, Node<'a>)> {
+    let mut children = let_declaration.children();
+
+    // Only identifier patterns are bound, so tuple or struct patterns are discarded.
+    let pattern = children
+        .by_ref()
+        .find(|child| child.kind_id() == Rust::Identifier)?
+        .utf8_text(source_code)?;
+
+    // The value is the child that follows the `=` token.
+    let value = children
+        .skip_while(|child| child.utf8_text(source_code) != Some("="))
+        .nth(1)?;
+
+    Some((pattern, value))
+}
+
+// Retrieves the value bound to the `identifier` node by the last `let`
+// declaration which precedes it, is visible from it, and is enabled by `cfg`.
+fn find_binding<'a>(
+    identifier: Node<'a>,
+    source_code: &'a [u8],
+    cfg: Option<&CfgSet>,
+) -> Option<Node<'a>> {
+    if identifier.kind_id() != Rust::Identifier {
+        return None;
+    }
+    let name = identifier.utf8_text(source_code)?;
+    let function = enclosing_function(identifier)?;
+
+    function
+        .all_occurrences(|n| n.kind_id() == Rust::LetDeclaration)
+        .into_iter()
+        .filter(|let_declaration| {
+            // The declaration must end before the identifier and its block must contain it.
+            let_declaration.end_byte() <= identifier.start_byte()
+                && let_declaration
+                    .parent()
+                    .map_or(false, |block| encloses(&block, &identifier))
+                && is_enabled(*let_declaration, cfg, source_code)
+        })
+        .filter_map(|let_declaration| {
+            let (pattern, value) = let_binding(let_declaration, source_code)?;
+            (pattern == name).then_some((let_declaration.start_byte(), value))
+        })
+        .max_by_key(|(start_byte, _)| *start_byte)
+        .map(|(_, value)| value)
+}
+
+// Resolves `expression` to the value it is bound to, following the `let`
+// declarations of the enclosing function which are enabled by `cfg`.
+//
+// If `expression` is not a bound identifier, it is returned unchanged.
+pub(crate) fn resolve_binding<'a>(
+    expression: Node<'a>,
+    source_code: &'a [u8],
+    cfg: Option<&CfgSet>,
+) -> Node<'a> {
+    let mut resolved = expression;
+    for _ in 0..MAX_BINDINGS {
+        match find_binding(resolved, source_code, cfg) {
+            Some(value) => resolved = value,
+            None => break,
+        }
+    }
+    resolved
+}
+
+// Retrieves the expression returned by a function, which is either
+// the tail expression of its body or the value of a final `return`.
+pub(crate) fn returned_expression<'a>(
+    function: Node<'a>,
+    source_code: &'a [u8],
+) -> Option<Node<'a>> {
+    let body = function.first_child(|child| child.kind_id() == Rust::Block)?;
+
+    // Get the last statement or expression of the body.
+    let last = body
+        .children()
+        .filter(|child| {
+            !matches!(
+                Rust::from(child.kind_id()),
+                Rust::LineComment | Rust::BlockComment
+            ) && !matches!(child.utf8_text(source_code), Some("{" | "}"))
+        })
+        .last()?;
+
+    match Rust::from(last.kind_id()) {
+        // `return value;`.
+        Rust::ExpressionStatement => last
+            .child(0)
+            .filter(|inner| inner.kind_id() == Rust::ReturnExpression)
+            .and_then(|inner| inner.child(inner.child_count() - 1)),
+        // `return value` without semicolon.
+        Rust::ReturnExpression => last.child(last.child_count() - 1),
+        // A statement returning nothing.
+        Rust::LetDeclaration | Rust::EmptyStatement => None,
+        // Tail expression.
+        _ => Some(last),
+    }
+}
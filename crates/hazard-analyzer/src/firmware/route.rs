@@ -0,0 +1,239 @@
+// This module defines functions
+// for extracting the route configuration of a device action,
+// such as `Route::put("/on").description("Turn light on.").inputs([..])`.
+
+use rust_code_analysis::{Node, Rust, Search};
+use serde::Serialize;
+
+use crate::modules::last_segment;
+
+use super::{
+    cfg::CfgSet,
+    chain::{get_arguments, instance_chain, is_argument},
+    resolve::resolve_binding,
+};
+
+// Type defining the routes of the device actions.
+const ROUTE_TYPE: &str = "Route";
+
+// Type defining the inputs of a route.
+const INPUT_TYPE: &str = "Input";
+
+// Input kinds whose value is a `(minimum, maximum, step, default)` tuple.
+const RANGE_INPUTS: &[&str] = &["rangeu64", "rangef64"];
+
+// Input kind taking a boolean value.
+const BOOLEAN_INPUT: &str = "boolean";
+
+// Range of an input.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InputRange {
+    // Minimum value.
+    pub(crate) minimum: f64,
+    // Maximum value.
+    pub(crate) maximum: f64,
+    // Increment step.
+    pub(crate) step: f64,
+}
+
+// RouteInput.
+//
+// Input declared by a route, such as `Input::rangef64("brightness", (0., 20., 0.1, 0.))`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RouteInput<'a> {
+    // Input name.
+    pub(crate) name: &'a str,
+    // Input kind, that is the name of the `Input` constructor.
+    pub(crate) kind: &'a str,
+    // Row and column of the input inside the file declaring it.
+    pub(crate) position: (usize, usize),
+    // Range of the input, if it is a range input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) range: Option<InputRange>,
+    // Default value of the input, as written in the source code
+    // without the quotes of string literals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) default: Option<&'a str>,
+}
+
+impl<'a> RouteInput<'a> {
+    #[inline(always)]
+    // Returns `true` if the input can only take a bounded set of values,
+    // that is a range or a boolean input.
+    pub(crate) fn is_bounded(&self) -> bool {
+        self.range.is_some() || self.kind == BOOLEAN_INPUT
+    }
+}
+
+// Route.
+//
+// Route configuration of a device action.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Route<'a> {
+    // HTTP method.
+    pub(crate) method: &'static str,
+    // Route path.
+    pub(crate) path: &'a str,
+    // Route description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<&'a str>,
+    // Inputs of the route, in declaration order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) inputs: Vec<RouteInput<'a>>,
+}
+
+#[inline(always)]
+// Converts the name of a `Route` constructor into an HTTP method.
+fn http_method(constructor: &str) -> Option<&'static str> {
+    match constructor {
+        "get" => Some("GET"),
+        "put" => Some("PUT"),
+        "post" => Some("POST"),
+        "delete" => Some("DELETE"),
+        _ => None,
+    }
+}
+
+#[inline(always)]
+// Retrieves the content of a string literal, such as `/on` from `"/on"`.
+fn string_literal<'a>(node: Node, source_code: &'a [u8]) -> Option<&'a str> {
+    if node.kind_id() != Rust::StringLiteral {
+        return None;
+    }
+    node.utf8_text(source_code)?
+        .strip_prefix('"')?
+        .strip_suffix('"')
+}
+
+#[inline(always)]
+// Parses a numeric literal, such as `20.`, `1_000` or `4u64`.
+fn parse_number(literal: &str) -> Option<f64> {
+    let literal = literal
+        .split_whitespace()
+        .collect::<String>()
+        .replace('_', "");
+    let literal = ["f32", "f64", "u8", "u16", "u32", "u64", "i32", "i64"]
+        .iter()
+        .find_map(|suffix| literal.strip_suffix(suffix))
+        .unwrap_or(&literal);
+
+    literal.parse().ok()
+}
+
+// Retrieves the range of an input from the values of its
+// `(minimum, maximum, step, default)` tuple.
+fn get_range(values: &[&str]) -> Option<InputRange> {
+    let [minimum, maximum, step, ..] = values else {
+        return None;
+    };
+
+    Some(InputRange {
+        minimum: parse_number(minimum)?,
+        maximum: parse_number(maximum)?,
+        step: parse_number(step)?,
+    })
+}
+
+// Retrieves an input from an `Input::kind(name, value)` call.
+fn get_input<'a>(call: Node<'a>, source_code: &'a [u8]) -> Option<RouteInput<'a>> {
+    let function = call
+        .child(0)
+        .filter(|function| function.kind_id() == Rust::ScopedIdentifier)?
+        .utf8_text(source_code)?;
+    let (path, kind) = function.rsplit_once("::")?;
+    if last_segment(path) != INPUT_TYPE {
+        return None;
+    }
+
+    let arguments = get_arguments(call);
+    let name = string_literal(*arguments.first()?, source_code)?;
+    let value = arguments.get(1);
+
+    // Range inputs take a `(minimum, maximum, step, default)` tuple.
+    let (range, default) = match value {
+        Some(tuple)
+            if RANGE_INPUTS.contains(&kind.trim()) && tuple.kind_id() == Rust::TupleExpression =>
+        {
+            let values: Vec<_> = tuple
+                .children()
+                .filter(is_argument)
+                .filter_map(|value| value.utf8_text(source_code))
+                .collect();
+            (get_range(&values), values.get(3).copied())
+        }
+        Some(value) => (
+            None,
+            string_literal(*value, source_code).or_else(|| value.utf8_text(source_code)),
+        ),
+        None => (None, None),
+    };
+
+    Some(RouteInput {
+        name,
+        kind: kind.trim(),
+        position: call.start_position(),
+        range,
+        default,
+    })
+}
+
+// Retrieves the inputs passed to `.input(..)` or `.inputs([..])`.
+fn get_inputs<'a>(
+    argument: Node<'a>,
+    source_code: &'a [u8],
+    cfg: Option<&CfgSet>,
+) -> Vec<RouteInput<'a>> {
+    let value = resolve_binding(argument, source_code, cfg);
+
+    let mut calls = value.all_occurrences(|n| n.kind_id() == Rust::CallExpression);
+    calls.sort_by_key(|call| call.start_byte());
+
+    calls
+        .into_iter()
+        .filter_map(|call| get_input(call, source_code))
+        .collect()
+}
+
+// Retrieves the route configuration passed to a `DeviceAction`,
+// resolving it through the `let` declarations of the enclosing function.
+//
+// Returns `None` if the configuration is not built through a `Route` constructor.
+pub(crate) fn get_route<'a>(
+    config: Node<'a>,
+    source_code: &'a [u8],
+    cfg: Option<&CfgSet>,
+) -> Option<Route<'a>> {
+    let value = resolve_binding(config, source_code, cfg);
+    let chain = instance_chain(value, source_code, cfg);
+
+    // The chain must start with a `Route::method(path)` call.
+    if last_segment(chain.receiver.utf8_text(source_code)?) != ROUTE_TYPE {
+        return None;
+    }
+    let constructor = chain.calls.first()?;
+    let method = http_method(constructor.name)?;
+    let path = string_literal(*constructor.arguments.first()?, source_code)?;
+
+    let description = chain
+        .call_named("description")
+        .and_then(|call| call.arguments.first())
+        .and_then(|argument| string_literal(*argument, source_code));
+
+    let inputs = chain
+        .calls
+        .iter()
+        .filter(|call| matches!(call.name, "input" | "inputs"))
+        .filter_map(|call| call.arguments.first())
+        .flat_map(|argument| get_inputs(*argument, source_code, cfg))
+        .collect();
+
+    Some(Route {
+        method,
+        path,
+        description,
+        inputs,
+    })
+}
@@ -0,0 +1,203 @@
+// This module defines the analysis of the lifecycle of a device instance,
+// checking whether the device is built and then run on an `AscotServer`,
+// such as `AscotServer::new(device).run_service(..)?.run().await`.
+
+use std::collections::HashSet;
+
+use rust_code_analysis::{Node, Rust, Search};
+use serde::Serialize;
+
+use super::{
+    cfg::is_enabled,
+    chain::{chain_top, get_arguments, instance_chain, method_chain, same_node, MethodChain},
+    resolve::{enclosing_function, returned_expression},
+    Context,
+};
+
+// Path of the function creating a server.
+const SERVER_NEW: &str = "AscotServer::new";
+
+// Status of a device instance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DeviceStatus {
+    // The device is never built through `build()`.
+    NotBuilt,
+    // The device is built, but never run on an `AscotServer`.
+    NotServed,
+    // The device is run on an `AscotServer` without a discovery service.
+    ServedWithoutDiscovery,
+    // The device is run on an `AscotServer` along with a discovery service.
+    Served,
+}
+
+impl DeviceStatus {
+    #[inline(always)]
+    // Returns `true` if the device is built and served.
+    pub(crate) fn is_served(self) -> bool {
+        matches!(self, Self::ServedWithoutDiscovery | Self::Served)
+    }
+}
+
+// Returns `true` if `call` is a call to `AscotServer::new`,
+// also when the server type has been imported under another name.
+fn is_server_new(call: Node, context: Context) -> bool {
+    let Some(path) = call
+        .child(0)
+        .filter(|function| function.kind_id() == Rust::ScopedIdentifier)
+        .and_then(|function| function.utf8_text(context.source_code()))
+    else {
+        return false;
+    };
+
+    let path = context
+        .symbols
+        .imports(&context.firmware_file.path)
+        .and_then(|imports| imports.resolve(path))
+        .unwrap_or_else(|| path.to_string());
+    let path = path.trim_start_matches("::");
+
+    path == SERVER_NEW || path.ends_with(&format!("::{SERVER_NEW}"))
+}
+
+// Retrieves the names of the methods called on a server, also through the variables
+// it is bound to, e.g. `let server = AscotServer::new(device); server.run().await`.
+fn server_calls<'a>(server: Node<'a>, function: Node<'a>, context: Context<'a>) -> Vec<&'a str> {
+    let source_code = context.source_code();
+    let chain = instance_chain(chain_top(server), source_code, context.cfg);
+    let mut calls: Vec<_> = chain.calls.iter().map(|call| call.name).collect();
+
+    // Get the method chains invoked on the variables holding the server.
+    let receivers = function.all_occurrences(|n| {
+        n.kind_id() == Rust::Identifier
+            && n.utf8_text(source_code)
+                .map_or(false, |variable| chain.variables.contains(variable))
+    });
+    for receiver in receivers {
+        let next = method_chain(chain_top(receiver), source_code);
+        if same_node(&next.receiver, &receiver) {
+            calls.extend(next.calls.iter().map(|call| call.name));
+        }
+    }
+
+    calls
+}
+
+// Returns `true` if `function` returns the device value whose outermost
+// expression is `top`, either directly, through one of the `variables`
+// holding it, or wrapped in `Ok(..)`.
+fn returns_device(
+    function: Node,
+    top: Node,
+    variables: &HashSet<&str>,
+    source_code: &[u8],
+) -> bool {
+    let Some(mut returned) = returned_expression(function, source_code) else {
+        return false;
+    };
+
+    // `Ok(device)`.
+    if returned.kind_id() == Rust::CallExpression
+        && returned
+            .child(0)
+            .and_then(|function| function.utf8_text(source_code))
+            == Some("Ok")
+    {
+        let Some(value) = get_arguments(returned).first().copied() else {
+            return false;
+        };
+        returned = value;
+    }
+
+    same_node(&returned, &top)
+        || (returned.kind_id() == Rust::Identifier
+            && returned
+                .utf8_text(source_code)
+                .map_or(false, |variable| variables.contains(variable)))
+}
+
+// Retrieves whether the device value, whose outermost expression is `top`
+// and which is held by `variables`, is run on an `AscotServer` along with
+// a discovery service, or `None` if it is never run.
+//
+// When the enclosing function returns the device, as in
+// `fn light() -> Device<()> { Light::new(..).build() }`, the value is followed
+// to the callers of the function, up to `depth` nested functions.
+fn server_discovery<'a>(
+    top: Node<'a>,
+    variables: &HashSet<&'a str>,
+    context: Context<'a>,
+    depth: usize,
+) -> Option<bool> {
+    let source_code = context.source_code();
+    let function = enclosing_function(top)?;
+
+    // Get the AscotServer::new() calls taking the device,
+    // either directly or through a variable holding it.
+    let servers = function.all_occurrences(|n| {
+        n.kind_id() == Rust::CallExpression
+            && is_server_new(*n, context)
+            && is_enabled(*n, context.cfg, source_code)
+            && get_arguments(*n).first().map_or(false, |argument| {
+                same_node(argument, &top)
+                    || (argument.kind_id() == Rust::Identifier
+                        && argument
+                            .utf8_text(source_code)
+                            .map_or(false, |variable| variables.contains(variable)))
+            })
+    });
+
+    // Check whether the servers are run, and along with a discovery service.
+    let discovery = servers
+        .into_iter()
+        .map(|server| server_calls(server, function, context))
+        .filter(|calls| calls.contains(&"run"))
+        .map(|calls| calls.contains(&"run_service"))
+        .max();
+
+    if discovery.is_some() || depth == 0 || !returns_device(function, top, variables, source_code) {
+        return discovery;
+    }
+
+    // Follow the returned device to the callers of the function.
+    let name = function
+        .first_child(|child| child.kind_id() == Rust::Identifier)?
+        .utf8_text(source_code)?;
+    let helper = context
+        .symbols
+        .function(&context.firmware_file.path, name, context.cfg)?;
+
+    context
+        .symbols
+        .callers(helper, context.cfg)
+        .into_iter()
+        .filter_map(|(firmware_file, call)| {
+            let context = Context {
+                firmware_file,
+                ..context
+            };
+            let top = chain_top(call);
+            let chain = instance_chain(top, context.source_code(), context.cfg);
+            server_discovery(top, &chain.variables, context, depth - 1)
+        })
+        .max()
+}
+
+// Retrieves the status of a device instance, whose outermost expression is `top`,
+// following the device value to the `AscotServer` of the enclosing function,
+// or of the functions calling it when the device is returned.
+pub(crate) fn get_device_status<'a>(
+    top: Node<'a>,
+    chain: &MethodChain<'a>,
+    context: Context<'a>,
+) -> DeviceStatus {
+    if chain.call_named("build").is_none() {
+        return DeviceStatus::NotBuilt;
+    }
+
+    match server_discovery(top, &chain.variables, context, context.helper_depth) {
+        Some(true) => DeviceStatus::Served,
+        Some(false) => DeviceStatus::ServedWithoutDiscovery,
+        None => DeviceStatus::NotServed,
+    }
+}
@@ -0,0 +1,310 @@
+// This module defines the symbol table
+// of a firmware crate.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use rust_code_analysis::{Node, Rust, Search};
+
+use crate::{
+    modules::{last_segment, module_name},
+    re::hazards_from_text,
+};
+
+use super::{
+    cfg::{cfg_predicates, is_enabled, CfgSet},
+    file::FirmwareFile,
+    imports::Imports,
+};
+
+// Const or static item declaring hazards.
+struct HazardsItem<'a> {
+    // File in which the item is declared.
+    path: &'a Path,
+    // Declared hazards.
+    hazards: HashSet<&'a str>,
+    // Predicates of the cfg attributes which apply to the item.
+    cfg: Vec<&'a str>,
+}
+
+// Function item.
+pub(crate) struct FunctionItem<'a> {
+    // File in which the function is declared.
+    pub(crate) firmware_file: &'a FirmwareFile<'a>,
+    // Function name.
+    pub(crate) name: &'a str,
+    // Row and column of the function inside the file.
+    pub(crate) position: (usize, usize),
+    // Whether the function is `async`.
+    pub(crate) is_async: bool,
+    // Start byte of the function, used to retrieve its node.
+    start_byte: usize,
+    // Predicates of the cfg attributes which apply to the function.
+    cfg: Vec<&'a str>,
+}
+
+impl<'a> FunctionItem<'a> {
+    // Retrieves the function node from the file AST.
+    pub(crate) fn node(&self) -> Option<Node<'a>> {
+        self.firmware_file.root().first_occurence(|n| {
+            n.kind_id() == Rust::FunctionItem && n.start_byte() == self.start_byte
+        })
+    }
+}
+
+// Symbol table of a firmware crate.
+//
+// It is built once for all the firmware files, so that
+// symbols can be resolved across modules.
+pub(crate) struct Symbols<'a> {
+    // Firmware files of the crate.
+    firmware_files: &'a [FirmwareFile<'a>],
+    // Const and static items declaring hazards, grouped by name.
+    hazards_items: HashMap<&'a str, Vec<HazardsItem<'a>>>,
+    // Function items, grouped by name.
+    functions: HashMap<&'a str, Vec<FunctionItem<'a>>>,
+    // Imports of each file.
+    imports: HashMap<&'a Path, Imports>,
+}
+
+// Retrieves the name and the hazards of a const or static item,
+// such as `const LIGHT_ON_HAZARDS: &[Hazard] = &[Hazard::FireHazard];`.
+fn hazards_item<'a>(item: Node<'a>, source_code: &'a [u8]) -> Option<(&'a str, HashSet<&'a str>)> {
+    let name = item
+        .first_child(|child| child.kind_id() == Rust::Identifier)?
+        .utf8_text(source_code)?;
+    let (declaration, value) = item.utf8_text(source_code)?.split_once('=')?;
+
+    // Only the items whose type involves `Hazard` declare hazards.
+    if !declaration.contains("Hazard") {
+        return None;
+    }
+
+    Some((name, hazards_from_text(value)?))
+}
+
+// Retrieves the predicates of the cfg attributes which apply to an item,
+// including the ones of the `mod` declarations of its file.
+fn item_cfg<'a>(item: Node<'a>, firmware_file: &'a FirmwareFile<'a>) -> Vec<&'a str> {
+    let mut cfg = cfg_predicates(item, &firmware_file.source_code);
+    cfg.extend(firmware_file.cfg.iter().map(String::as_str));
+    cfg
+}
+
+// Creates a function item starting from its node.
+fn function_item<'a>(
+    item: Node<'a>,
+    firmware_file: &'a FirmwareFile<'a>,
+) -> Option<FunctionItem<'a>> {
+    let source_code = &firmware_file.source_code;
+    let name = item
+        .first_child(|child| child.kind_id() == Rust::Identifier)?
+        .utf8_text(source_code)?;
+
+    // Get the `async` keyword among the function modifiers.
+    let is_async = item
+        .first_child(|child| child.kind_id() == Rust::FunctionModifiers)
+        .and_then(|modifiers| modifiers.utf8_text(source_code))
+        .map_or(false, |modifiers| {
+            modifiers
+                .split_whitespace()
+                .any(|modifier| modifier == "async")
+        });
+
+    Some(FunctionItem {
+        firmware_file,
+        name,
+        position: item.start_position(),
+        is_async,
+        start_byte: item.start_byte(),
+        cfg: item_cfg(item, firmware_file),
+    })
+}
+
+impl<'a> Symbols<'a> {
+    // Builds the symbol table of the given firmware files.
+    pub(crate) fn new(firmware_files: &'a [FirmwareFile<'a>]) -> Self {
+        let mut hazards_items: HashMap<&str, Vec<HazardsItem>> = HashMap::new();
+        let mut functions: HashMap<&str, Vec<FunctionItem>> = HashMap::new();
+        let mut imports = HashMap::new();
+
+        for firmware_file in firmware_files {
+            let root = firmware_file.root();
+            let source_code = &firmware_file.source_code;
+            let path = firmware_file.path.as_ref();
+
+            root.all_occurrences(|n| {
+                matches!(Rust::from(n.kind_id()), Rust::ConstItem | Rust::StaticItem)
+            })
+            .into_iter()
+            .filter_map(|item| {
+                let (name, hazards) = hazards_item(item, source_code)?;
+                let cfg = item_cfg(item, firmware_file);
+                Some((name, HazardsItem { path, hazards, cfg }))
+            })
+            .for_each(|(name, item)| hazards_items.entry(name).or_default().push(item));
+
+            root.all_occurrences(|n| n.kind_id() == Rust::FunctionItem)
+                .into_iter()
+                .filter_map(|item| function_item(item, firmware_file))
+                .for_each(|function| functions.entry(function.name).or_default().push(function));
+
+            imports.insert(path, Imports::new(root, source_code));
+        }
+
+        Self {
+            firmware_files,
+            hazards_items,
+            functions,
+            imports,
+        }
+    }
+
+    // Retrieves the imports of the file at `path`.
+    #[inline(always)]
+    pub(crate) fn imports(&self, path: &Path) -> Option<&Imports> {
+        self.imports.get(path)
+    }
+
+    // Retrieves the hazards declared by the const or static item
+    // which `reference` refers to inside the file at `path`.
+    #[inline(always)]
+    pub(crate) fn hazards(
+        &self,
+        path: &Path,
+        reference: &str,
+        cfg: Option<&CfgSet>,
+    ) -> Option<&HashSet<&'a str>> {
+        self.lookup(path, reference, &self.hazards_items, cfg, |item| {
+            (item.path, &item.cfg)
+        })
+        .map(|item| &item.hazards)
+    }
+
+    // Retrieves the function item which `reference` refers to inside the file at `path`.
+    #[inline(always)]
+    pub(crate) fn function(
+        &self,
+        path: &Path,
+        reference: &str,
+        cfg: Option<&CfgSet>,
+    ) -> Option<&FunctionItem<'a>> {
+        self.lookup(path, reference, &self.functions, cfg, |function| {
+            (function.firmware_file.path.as_ref(), &function.cfg)
+        })
+    }
+
+    // Retrieves the calls to `function` inside the crate, along with
+    // the files containing them, discarding the calls disabled by `cfg`.
+    pub(crate) fn callers(
+        &self,
+        function: &FunctionItem<'a>,
+        cfg: Option<&CfgSet>,
+    ) -> Vec<(&'a FirmwareFile<'a>, Node<'a>)> {
+        self.firmware_files
+            .iter()
+            .filter(|firmware_file| firmware_file.is_enabled(cfg))
+            .flat_map(|firmware_file| {
+                let source_code = &firmware_file.source_code;
+                firmware_file
+                    .root()
+                    .all_occurrences(|n| {
+                        n.kind_id() == Rust::CallExpression
+                            && n.child(0)
+                                .filter(|callee| {
+                                    matches!(
+                                        Rust::from(callee.kind_id()),
+                                        Rust::Identifier | Rust::ScopedIdentifier
+                                    )
+                                })
+                                .and_then(|callee| callee.utf8_text(source_code))
+                                .and_then(|callee| self.function(&firmware_file.path, callee, cfg))
+                                .map_or(false, |callee| std::ptr::eq(callee, function))
+                            && is_enabled(*n, cfg, source_code)
+                    })
+                    .into_iter()
+                    .map(move |call| (firmware_file, call))
+            })
+            .collect()
+    }
+
+    // Returns `true` if the crate declares at least one function
+    // named as the last segment of `reference`.
+    #[inline(always)]
+    pub(crate) fn has_function(&self, reference: &str) -> bool {
+        self.functions.contains_key(last_segment(reference))
+    }
+
+    // Retrieves the item which `reference` refers to inside the file at `path`,
+    // where `item_info` returns the file in which an item is declared
+    // and the predicates of its cfg attributes.
+    //
+    // Items disabled by `cfg` are discarded, while ambiguous references,
+    // matching items with the same name in different modules, are not resolved.
+    fn lookup<'s, T>(
+        &self,
+        path: &Path,
+        reference: &str,
+        items: &'s HashMap<&'a str, Vec<T>>,
+        cfg: Option<&CfgSet>,
+        item_info: impl Fn(&T) -> (&Path, &[&'a str]),
+    ) -> Option<&'s T> {
+        let imports = self.imports(path);
+
+        // Expand the reference through the file imports.
+        let full_path = imports
+            .and_then(|imports| imports.resolve(reference))
+            .unwrap_or_else(|| reference.split_whitespace().collect());
+        let items: Vec<&T> = items
+            .get(last_segment(&full_path))?
+            .iter()
+            .filter(|item| cfg.map_or(true, |cfg| cfg.evaluate_all(item_info(item).1)))
+            .collect();
+
+        let candidates: Vec<_> = match full_path.rsplit_once("::") {
+            // A qualified path has to match the module in which the item is declared.
+            Some((parent, _)) => {
+                let module = last_segment(parent);
+                items
+                    .into_iter()
+                    .filter(|item| {
+                        matches!(module, "crate" | "self" | "super")
+                            || module_name(item_info(item).0) == Some(module)
+                    })
+                    .collect()
+            }
+            None => {
+                // An item declared in the same file.
+                if let Some(item) = items.iter().copied().find(|item| item_info(item).0 == path) {
+                    return Some(item);
+                }
+
+                // An item imported through a glob.
+                let globbed: Vec<_> = items
+                    .iter()
+                    .copied()
+                    .filter(|item| {
+                        imports.map_or(false, |imports| {
+                            imports.globs().iter().any(|glob| {
+                                module_name(item_info(item).0) == Some(last_segment(glob))
+                            })
+                        })
+                    })
+                    .collect();
+
+                if globbed.is_empty() {
+                    items
+                } else {
+                    globbed
+                }
+            }
+        };
+
+        match candidates.as_slice() {
+            [item] => Some(*item),
+            _ => None,
+        }
+    }
+}
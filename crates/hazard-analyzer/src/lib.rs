@@ -8,26 +8,247 @@
 mod concurrent;
 mod device;
 mod firmware;
+mod modules;
+mod policy;
 mod print;
 mod re;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use code_certifier::error::Result;
-use code_certifier::git::{ascot_devices, ascot_firmware};
+use code_certifier::error::{Error, ErrorKind, Result};
+use code_certifier::git::{ascot_devices, ascot_firmware, ascot_hazards};
 use code_certifier::manifest::{check_manifest_path, create_manifest};
 use concurrent::ConcurrentRunner;
-use device::{file::get_device_files, DeviceProducer};
-use firmware::{file::get_fw_files, Analyzer};
-use print::print_manifest;
+use device::{
+    file::get_device_files, hazard_mismatches, hazards::HazardCatalog, lint::LintManifest,
+    AscotDevice, AscotDevices, DeviceProducer,
+};
+use firmware::{
+    cfg::{referenced_features, CfgSet},
+    file::{get_fw_files, get_workspace_members, FirmwareFiles},
+    symbols::Symbols,
+    Analyzer, Device, FeatureCombination, Manifest, PackageManifest, WorkspaceManifest,
+};
+use policy::Policy;
+use print::{print_lint_manifest, print_manifest, print_warning, print_workspace_manifest};
+
+/// Default maximum number of nested helper functions
+/// followed while resolving a device action.
+pub const DEFAULT_HELPER_DEPTH: usize = 4;
+
+/// Options of the firmware analysis.
+#[derive(Debug, Clone)]
+pub struct AnalysisOptions {
+    /// Maximum number of nested helper functions followed while resolving
+    /// a device action, such as `fn light_on_action() -> DeviceAction<..>`.
+    ///
+    /// If set to `0`, helper functions are not analyzed.
+    pub helper_depth: usize,
+    /// Active features used to evaluate the `#[cfg(..)]` attributes of the firmware,
+    /// excluding the code they disable from the analysis.
+    ///
+    /// If `None` and `cfg` is empty, cfg attributes are not evaluated
+    /// and all the code is analyzed.
+    pub features: Option<Vec<String>>,
+    /// Active cfg options, such as `unix` or `target_os = "linux"`,
+    /// used to evaluate the `#[cfg(..)]` attributes of the firmware.
+    pub cfg: Vec<String>,
+    /// If set, the firmware is also analyzed with each combination of the features
+    /// referenced by its cfg attributes, in addition to the active ones,
+    /// reporting the combinations which are not compliant.
+    pub all_feature_combinations: bool,
+    /// Path to a `TOML` policy file, defining for example the hazards
+    /// which cannot be exposed through each HTTP method.
+    ///
+    /// If `None`, the default policy is used, which forbids
+    /// any hazard on the actions exposed through `GET` routes.
+    pub policy_path: Option<PathBuf>,
+    /// If set, the bodies of the action handlers are analyzed, along with
+    /// the functions of the firmware they call up to this number of nested calls,
+    /// reporting panicking constructs, `unsafe` blocks and, for `async` handlers,
+    /// blocking calls of the standard library such as `std::thread::sleep`.
+    ///
+    /// If set to `0`, only the handler bodies are analyzed.
+    pub handler_body_depth: Option<usize>,
+    /// Path to the file of `ascot-library` declaring the `Hazard` enum,
+    /// whose variants are the valid hazard names. Unknown hazards
+    /// used by the devices or by the firmware are reported along with
+    /// the closest valid name.
+    ///
+    /// If `None`, the file of the cloned `ascot-firmware` repository is used
+    /// when no devices path is given, otherwise hazard names are not checked.
+    /// A missing or invalid file of the cloned repository only disables the check,
+    /// while a missing or invalid file given through this option is an error.
+    pub hazards_path: Option<PathBuf>,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            helper_depth: DEFAULT_HELPER_DEPTH,
+            features: None,
+            cfg: Vec::new(),
+            all_feature_combinations: false,
+            policy_path: None,
+            handler_body_depth: None,
+            hazards_path: None,
+        }
+    }
+}
+
+// Maximum number of features whose combinations are analyzed.
+const MAX_COMBINATION_FEATURES: usize = 10;
+
+// Active features and cfg options of the analyses of a firmware.
+struct FirmwareCfg<'a> {
+    // Cfg of the main analysis, if cfg attributes are evaluated.
+    base: Option<CfgSet>,
+    // Active features and cfg of each feature combination.
+    combinations: Vec<(Vec<&'a str>, CfgSet)>,
+}
+
+impl<'a> FirmwareCfg<'a> {
+    fn new(firmware_files: &'a FirmwareFiles<'a>, options: &'a AnalysisOptions) -> Result<Self> {
+        let active_features: Vec<&str> = options
+            .features
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        let base = (options.features.is_some() || !options.cfg.is_empty())
+            .then(|| CfgSet::new(active_features.iter().copied(), &options.cfg));
+
+        if !options.all_feature_combinations {
+            return Ok(Self {
+                base,
+                combinations: Vec::new(),
+            });
+        }
+
+        // Get the features referenced by the firmware which are not already active.
+        let features: Vec<_> = referenced_features(&firmware_files.files)
+            .into_iter()
+            .filter(|feature| !active_features.contains(feature))
+            .collect();
+        if features.len() > MAX_COMBINATION_FEATURES {
+            return Err(Error::new(
+                ErrorKind::Analysis,
+                format!(
+                    "Too many features to combine: {}, the maximum is {MAX_COMBINATION_FEATURES}",
+                    features.len()
+                ),
+            ));
+        }
+
+        // Each bit of the mask enables a feature.
+        let combinations = (0..1usize << features.len())
+            .map(|mask| {
+                let mut combination = active_features.clone();
+                combination.extend(
+                    features
+                        .iter()
+                        .enumerate()
+                        .filter(|(bit, _)| mask & (1 << bit) != 0)
+                        .map(|(_, feature)| *feature),
+                );
+                combination.sort_unstable();
+
+                let cfg = CfgSet::new(combination.iter().copied(), &options.cfg);
+                (combination, cfg)
+            })
+            .collect();
+
+        Ok(Self { base, combinations })
+    }
+}
+
+// Ascot devices and rules a firmware is certified against.
+struct Certification<'a> {
+    // Ascot devices.
+    ascot_devices: &'a [AscotDevice<'a>],
+    // Certification policy.
+    policy: &'a Policy,
+    // Catalog of the valid hazards, if hazard names are checked.
+    hazard_catalog: Option<&'a HazardCatalog>,
+}
+
+// Analyzes the files of a firmware and builds their manifest.
+fn analyze_firmware<'a>(
+    certification: &Certification<'a>,
+    firmware_files: &'a FirmwareFiles<'a>,
+    symbols: &'a Symbols<'a>,
+    firmware_cfg: &'a FirmwareCfg<'a>,
+    options: &AnalysisOptions,
+    n_threads: usize,
+) -> Result<Manifest<'a>> {
+    let files = Analyzer::new(
+        certification.ascot_devices,
+        symbols,
+        options.helper_depth,
+        firmware_cfg.base.as_ref(),
+        certification.policy,
+        options.handler_body_depth,
+        certification.hazard_catalog,
+    )
+    .run(&firmware_files.files, n_threads)?;
+
+    // Analyze the firmware with each combination of features.
+    let feature_combinations = firmware_cfg
+        .combinations
+        .iter()
+        .map(|(features, cfg)| {
+            let files = Analyzer::new(
+                certification.ascot_devices,
+                symbols,
+                options.helper_depth,
+                Some(cfg),
+                certification.policy,
+                options.handler_body_depth,
+                certification.hazard_catalog,
+            )
+            .run(&firmware_files.files, n_threads)?;
+            Ok(FeatureCombination::new(features, &files))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Describe the hazards referenced by the devices.
+    let hazards = certification
+        .hazard_catalog
+        .map(|hazard_catalog| {
+            hazard_catalog.describe(
+                files
+                    .iter()
+                    .flat_map(|file| &file.devices)
+                    .flat_map(Device::referenced_hazards),
+            )
+        })
+        .unwrap_or_default();
+
+    Ok(Manifest {
+        files,
+        unreachable_files: &firmware_files.unreachable_files,
+        feature_combinations,
+        hazards,
+        unknown_device_hazards: &[],
+        hazard_mismatches: &[],
+        device_catalog: &[],
+    })
+}
 
 /// Creates a json manifest with all
 /// the device instances inside the firmware.
 ///
 /// - `devices_path`: path to **ascot-firmware/ascot-axum/devices/**.
-/// - `firmware_path`: path to the firmware to analyze.
+/// - `firmware_path`: path to the firmware to analyze. It can be a single file,
+///   a directory, or the `Cargo.toml` manifest of a crate. In the last case,
+///   only the files reachable from the crate targets through `mod` declarations
+///   are analyzed, while the other ones are reported as unreachable.
+///   When the manifest defines a workspace, each member package is analyzed
+///   as a separate firmware and the output manifest is grouped by package,
+///   along with a compliance verdict for each of them.
 /// - `manifest_path`: path to the output manifest. It should be a `JSON` file.
 /// - `quiet`: if set to true, the analysis output will not be printed on the terminal.
+/// - `options`: options of the firmware analysis.
 ///
 /// If `devices_path` is `None` the tool will clone the
 /// [ascot-firmware](https://github.com/SoftengPoliTo/ascot-firmware) repository
@@ -37,6 +258,7 @@ pub fn hazard_analyzer<D: AsRef<Path>>(
     firmware_path: &Path,
     manifest_path: &Path,
     quiet: bool,
+    options: &AnalysisOptions,
 ) -> Result<()> {
     // Check manifest path.
     check_manifest_path(manifest_path)?;
@@ -44,24 +266,136 @@ pub fn hazard_analyzer<D: AsRef<Path>>(
     // Set number of threads.
     let n_threads = (rayon::current_num_threads() - 1).max(1);
 
-    // Get device files.
-    let device_files = match devices_path {
-        Some(devices_path) => get_device_files(devices_path.as_ref())?,
+    // Get device files and the path to the hazards file.
+    let (device_files, hazards_path) = match devices_path {
+        Some(devices_path) => (
+            get_device_files(devices_path.as_ref())?,
+            options.hazards_path.clone(),
+        ),
         None => {
             let ascot_firmware = ascot_firmware()?;
+            let hazards_path = options
+                .hazards_path
+                .clone()
+                .unwrap_or_else(|| ascot_hazards(&ascot_firmware));
             let devices_path = ascot_devices(ascot_firmware);
-            get_device_files(&devices_path)?
+            (get_device_files(&devices_path)?, Some(hazards_path))
         }
     };
 
     // Get ascot devices.
-    let ascot_devices = DeviceProducer::new().run(&device_files, n_threads)?;
+    let AscotDevices {
+        devices: ascot_devices,
+        reports: device_catalog,
+        ..
+    } = DeviceProducer::new().run(&device_files, n_threads)?;
+
+    // Load the catalog of the valid hazards and check the device hazards.
+    let hazard_catalog = match hazards_path.as_deref().map(HazardCatalog::load) {
+        Some(Ok(hazard_catalog)) => Some(hazard_catalog),
+        // The default hazards file of the cloned repository may be missing or
+        // not parsable, which only disables the check of hazard names.
+        Some(Err(error)) if options.hazards_path.is_none() => {
+            print_warning(&format!("Hazard names are not checked: {error}"))?;
+            None
+        }
+        Some(Err(error)) => return Err(error),
+        None => None,
+    };
+    let unknown_device_hazards = hazard_catalog
+        .as_ref()
+        .map(|hazard_catalog| hazard_catalog.unknown_device_hazards(&ascot_devices))
+        .unwrap_or_default();
+
+    // Check the hazards of the device mandatory actions against their consts.
+    let hazard_mismatches = hazard_mismatches(&ascot_devices);
+
+    // Load the certification policy.
+    let policy = Policy::load(options.policy_path.as_deref())?;
+
+    let certification = Certification {
+        ascot_devices: &ascot_devices,
+        policy: &policy,
+        hazard_catalog: hazard_catalog.as_ref(),
+    };
+
+    // Analyze each package of a workspace as a separate firmware,
+    // using the same Ascot devices.
+    if let Some(members) = get_workspace_members(firmware_path)? {
+        // Get firmware files and symbol table of each package.
+        let members_files = members
+            .iter()
+            .map(|member| get_fw_files(&member.manifest_path))
+            .collect::<Result<Vec<_>>>()?;
+        let members_symbols: Vec<_> = members_files
+            .iter()
+            .map(|firmware_files| Symbols::new(&firmware_files.files))
+            .collect();
+        let members_cfg = members_files
+            .iter()
+            .map(|firmware_files| FirmwareCfg::new(firmware_files, options))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Get the manifest of each package.
+        let mut packages = members
+            .iter()
+            .zip(&members_files)
+            .zip(&members_symbols)
+            .zip(&members_cfg)
+            .map(|(((member, firmware_files), symbols), firmware_cfg)| {
+                let manifest = analyze_firmware(
+                    &certification,
+                    firmware_files,
+                    symbols,
+                    firmware_cfg,
+                    options,
+                    n_threads,
+                )?;
+                Ok(PackageManifest {
+                    name: &member.name,
+                    compliant: manifest.is_compliant(),
+                    manifest,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        packages.sort_by(|first, second| first.name.cmp(second.name));
+        let manifest = WorkspaceManifest {
+            packages,
+            unknown_device_hazards: &unknown_device_hazards,
+            hazard_mismatches: &hazard_mismatches,
+            device_catalog: &device_catalog,
+        };
+
+        // Print the manifest.
+        if !quiet {
+            print_workspace_manifest(&manifest)?;
+        }
+
+        // Create the manifest json.
+        return create_manifest(&manifest, manifest_path);
+    }
 
     // Get firmware files.
     let firmware_files = get_fw_files(firmware_path)?;
 
+    // Get the symbol table of the firmware.
+    let symbols = Symbols::new(&firmware_files.files);
+
+    // Get the features and cfg options of the analyses.
+    let firmware_cfg = FirmwareCfg::new(&firmware_files, options)?;
+
     // Get the manifest.
-    let manifest = Analyzer::new(&ascot_devices).run(&firmware_files, n_threads)?;
+    let mut manifest = analyze_firmware(
+        &certification,
+        &firmware_files,
+        &symbols,
+        &firmware_cfg,
+        options,
+        n_threads,
+    )?;
+    manifest.unknown_device_hazards = &unknown_device_hazards;
+    manifest.hazard_mismatches = &hazard_mismatches;
+    manifest.device_catalog = &device_catalog;
 
     // Print the manifest.
     if !quiet {
@@ -71,3 +405,51 @@ pub fn hazard_analyzer<D: AsRef<Path>>(
     // Create the manifest json.
     create_manifest(&manifest, manifest_path)
 }
+
+/// Checks the consistency of the `Ascot` device definitions and creates
+/// a json manifest with the inconsistencies found.
+///
+/// Each device is checked so that its mandatory hazards are included in
+/// its allowed hazards, each mandatory action has a hazard const named after it,
+/// each `Actions` variant has a method named after it and is inserted into
+/// the mandatory actions inside `new()`, and each hazard const is used.
+///
+/// - `devices_path`: path to **ascot-firmware/ascot-axum/devices/**.
+/// - `manifest_path`: path to the output manifest. It should be a `JSON` file.
+/// - `quiet`: if set to true, the inconsistencies will not be printed on the terminal.
+///
+/// If `devices_path` is `None` the tool will clone the
+/// [ascot-firmware](https://github.com/SoftengPoliTo/ascot-firmware) repository
+/// and use the **ascot-axum/devices/** inside it.
+pub fn lint_devices<D: AsRef<Path>>(
+    devices_path: Option<D>,
+    manifest_path: &Path,
+    quiet: bool,
+) -> Result<()> {
+    // Check manifest path.
+    check_manifest_path(manifest_path)?;
+
+    // Set number of threads.
+    let n_threads = (rayon::current_num_threads() - 1).max(1);
+
+    // Get device files.
+    let device_files = match devices_path {
+        Some(devices_path) => get_device_files(devices_path.as_ref())?,
+        None => get_device_files(&ascot_devices(ascot_firmware()?))?,
+    };
+
+    // Check the device definitions.
+    let AscotDevices { lints, .. } = DeviceProducer::new().run(&device_files, n_threads)?;
+    let manifest = LintManifest {
+        consistent: lints.is_empty(),
+        files: &lints,
+    };
+
+    // Print the manifest.
+    if !quiet {
+        print_lint_manifest(&manifest)?;
+    }
+
+    // Create the manifest json.
+    create_manifest(&manifest, manifest_path)
+}
@@ -0,0 +1,171 @@
+// This module defines functions
+// for following the `mod` declarations of a file,
+// so that the module tree of a crate can be built,
+// along with helpers for module names and paths.
+
+use std::path::{Component, Path, PathBuf};
+
+use rust_code_analysis::{Node, Rust};
+
+use crate::re::PATH_ATTRIBUTE_RE;
+
+// Normalizes a path by removing `.` and `..` components,
+// without accessing the file system.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            // Keep only the leading `.`.
+            Component::CurDir if normalized.as_os_str().is_empty() => normalized.push("."),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                let is_normal =
+                    matches!(normalized.components().last(), Some(Component::Normal(_)));
+                if is_normal {
+                    normalized.pop();
+                } else {
+                    normalized.push("..");
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+#[inline(always)]
+// Returns the last segment of a path.
+pub(crate) fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path).trim()
+}
+
+#[inline(always)]
+// Returns the name of the module defined by a file, which is the file stem
+// or the directory name for a `mod.rs` file.
+pub(crate) fn module_name(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    if stem == "mod" {
+        path.parent()?.file_name()?.to_str()
+    } else {
+        Some(stem)
+    }
+}
+
+// Module declared through `mod name;`.
+pub(crate) struct Submodule<'a> {
+    // Path of the module file, which may not exist.
+    pub(crate) path: PathBuf,
+    // `mod name;` item declaring the module.
+    pub(crate) item: Node<'a>,
+}
+
+#[inline(always)]
+// Retrieves the path of a `#[path = "file.rs"]` attribute.
+fn path_attribute<'a>(attribute: Node, source_code: &'a [u8]) -> Option<&'a str> {
+    let text = attribute.utf8_text(source_code)?;
+    PATH_ATTRIBUTE_RE
+        .captures(text)
+        .and_then(|capture| capture.get(1))
+        .map(|path| path.as_str())
+}
+
+// Retrieves the files of the modules declared inside `container`,
+// which is either the root of a file or the body of an inline module.
+//
+// - `directory`: directory in which the files of the modules are searched.
+// - `attribute_directory`: directory to which `#[path]` attributes are relative.
+fn collect_submodules<'a>(
+    container: Node<'a>,
+    directory: &Path,
+    attribute_directory: &Path,
+    source_code: &[u8],
+    submodules: &mut Vec<Submodule<'a>>,
+) {
+    // Path of the last `#[path]` attribute preceding an item.
+    let mut path = None;
+
+    for child in container.children() {
+        match Rust::from(child.kind_id()) {
+            Rust::AttributeItem => {
+                if let Some(attribute) = path_attribute(child, source_code) {
+                    path = Some(attribute);
+                }
+                continue;
+            }
+            Rust::LineComment | Rust::BlockComment => continue,
+            Rust::ModItem => {
+                let Some(name) = child
+                    .first_child(|n| n.kind_id() == Rust::Identifier)
+                    .and_then(|name| name.utf8_text(source_code))
+                else {
+                    path = None;
+                    continue;
+                };
+
+                match child.first_child(|n| n.kind_id() == Rust::DeclarationList) {
+                    // `mod name { .. }`.
+                    Some(body) => {
+                        let directory = directory.join(path.unwrap_or(name));
+                        collect_submodules(body, &directory, &directory, source_code, submodules);
+                    }
+                    // `mod name;`.
+                    //
+                    // When the file of the module is missing, its expected path
+                    // is kept, so that callers can report it.
+                    None => {
+                        let file = match path {
+                            Some(path) => attribute_directory.join(path),
+                            None => {
+                                let file = directory.join(format!("{name}.rs"));
+                                let mod_file = directory.join(name).join("mod.rs");
+                                if !file.is_file() && mod_file.is_file() {
+                                    mod_file
+                                } else {
+                                    file
+                                }
+                            }
+                        };
+                        submodules.push(Submodule {
+                            path: normalize_path(&file),
+                            item: child,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        path = None;
+    }
+}
+
+// Retrieves the files of the modules declared through `mod name;`
+// inside the file at `path`, also when declared inside inline modules.
+//
+// The files of the modules are returned also when they do not exist.
+//
+// `is_crate_root` must be `true` for the root file of a crate target,
+// e.g. `src/lib.rs` or `src/main.rs`, whose submodules are searched
+// in its directory as for `mod.rs` files.
+pub(crate) fn get_submodules<'a>(
+    path: &Path,
+    is_crate_root: bool,
+    root: Node<'a>,
+    source_code: &[u8],
+) -> Vec<Submodule<'a>> {
+    let mut submodules = Vec::new();
+    let Some(parent) = path.parent() else {
+        return submodules;
+    };
+
+    // The submodules of a `name.rs` file are searched in the `name/` directory.
+    let directory = match path.file_stem().and_then(|stem| stem.to_str()) {
+        Some(stem) if !is_crate_root && stem != "mod" => parent.join(stem),
+        _ => parent.to_path_buf(),
+    };
+
+    collect_submodules(root, &directory, parent, source_code, &mut submodules);
+
+    submodules
+}
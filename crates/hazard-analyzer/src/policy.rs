@@ -0,0 +1,166 @@
+// This module defines the certification policy
+// the actions of a firmware are checked against,
+// which can be loaded from a TOML file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use code_certifier::error::Result;
+use serde::Deserialize;
+
+// Hazard matching any hazard inside a policy.
+const ANY_HAZARD: &str = "*";
+
+// Bounds of the range inputs of the actions.
+//
+// Each field but the bounds restricts the inputs the rule applies to,
+// so that a rule without `device`, `action` and `input` applies to any range input.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct InputRangeRule {
+    // Device name.
+    device: Option<String>,
+    // Action name.
+    action: Option<String>,
+    // Input name.
+    input: Option<String>,
+    // Minimum allowed value of the range.
+    pub(crate) minimum: Option<f64>,
+    // Maximum allowed value of the range.
+    pub(crate) maximum: Option<f64>,
+}
+
+impl InputRangeRule {
+    #[inline(always)]
+    // Returns `true` if the rule applies to the input of a device action.
+    fn matches(&self, device: &str, action: &str, input: &str) -> bool {
+        [
+            (&self.device, device),
+            (&self.action, action),
+            (&self.input, input),
+        ]
+        .into_iter()
+        .all(|(expected, name)| {
+            expected
+                .as_deref()
+                .map_or(true, |expected| expected == name)
+        })
+    }
+
+    #[inline(always)]
+    // Returns `true` if the range is within the bounds of the rule.
+    pub(crate) fn allows(&self, minimum: f64, maximum: f64) -> bool {
+        self.minimum.map_or(true, |bound| minimum >= bound)
+            && self.maximum.map_or(true, |bound| maximum <= bound)
+    }
+}
+
+// Certification policy.
+//
+// ```toml
+// # Hazards whose actions cannot declare unbounded inputs.
+// bounded-input-hazards = ["FireHazard"]
+//
+// # Hazards which cannot be exposed through each HTTP method,
+// # where "*" matches any hazard.
+// [http-methods]
+// GET = ["*"]
+// DELETE = ["FireHazard", "PowerSurge"]
+//
+// # Bounds of the range inputs of the actions.
+// [[input-ranges]]
+// device = "Fridge"
+// action = "increase_temperature"
+// input = "increment"
+// maximum = 4.0
+// ```
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Policy {
+    // Hazards whose actions cannot declare unbounded inputs.
+    #[serde(default)]
+    bounded_input_hazards: HashSet<String>,
+    // Hazards which cannot be exposed through each HTTP method.
+    #[serde(default = "default_http_methods")]
+    http_methods: HashMap<String, HashSet<String>>,
+    // Bounds of the range inputs of the actions.
+    #[serde(default)]
+    input_ranges: Vec<InputRangeRule>,
+}
+
+// By default, safe methods cannot expose any hazard.
+fn default_http_methods() -> HashMap<String, HashSet<String>> {
+    HashMap::from([("GET".to_string(), HashSet::from([ANY_HAZARD.to_string()]))])
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            bounded_input_hazards: HashSet::new(),
+            http_methods: default_http_methods(),
+            input_ranges: Vec::new(),
+        }
+    }
+}
+
+impl Policy {
+    // Loads the policy from a TOML file, or the default policy if `path` is `None`.
+    pub(crate) fn load(path: Option<&Path>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        let mut policy: Self = toml::from_str(&std::fs::read_to_string(path)?)?;
+
+        // HTTP methods are case-insensitive.
+        policy.http_methods = policy
+            .http_methods
+            .into_iter()
+            .map(|(method, hazards)| (method.to_uppercase(), hazards))
+            .collect();
+
+        Ok(policy)
+    }
+
+    // Returns the hazards, sorted by name, which cannot be exposed through `method`.
+    pub(crate) fn forbidden_method_hazards<'a>(
+        &self,
+        method: &str,
+        hazards: &HashSet<&'a str>,
+    ) -> Vec<&'a str> {
+        let Some(forbidden) = self.http_methods.get(method) else {
+            return Vec::new();
+        };
+
+        let mut hazards: Vec<_> = hazards
+            .iter()
+            .filter(|hazard| forbidden.contains(ANY_HAZARD) || forbidden.contains(**hazard))
+            .copied()
+            .collect();
+        hazards.sort_unstable();
+        hazards
+    }
+
+    // Returns the hazards, sorted by name, whose actions cannot declare unbounded inputs.
+    pub(crate) fn bounded_input_hazards<'a>(&self, hazards: &HashSet<&'a str>) -> Vec<&'a str> {
+        let mut hazards: Vec<_> = hazards
+            .iter()
+            .filter(|hazard| self.bounded_input_hazards.contains(**hazard))
+            .copied()
+            .collect();
+        hazards.sort_unstable();
+        hazards
+    }
+
+    // Returns the range rules which apply to the input of a device action.
+    pub(crate) fn input_range_rules<'p>(
+        &'p self,
+        device: &'p str,
+        action: &'p str,
+        input: &'p str,
+    ) -> impl Iterator<Item = &'p InputRangeRule> + 'p {
+        self.input_ranges
+            .iter()
+            .filter(move |rule| rule.matches(device, action, input))
+    }
+}
@@ -3,7 +3,15 @@ use std::io::Write;
 
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
-use crate::firmware::FileManifest;
+use crate::device::{
+    hazards::{UnknownDeviceHazards, UnknownHazard},
+    lint::{LintIssue, LintManifest},
+    DeviceFileReport, DeviceStyle, HazardMismatch,
+};
+use crate::firmware::{
+    ActionLocation, DeviceStatus, Diagnostic, FindingKind, Handler, HandlerIssue, HelperFunction,
+    InputViolationKind, Manifest, Route, WorkspaceManifest,
+};
 
 const RED: Color = Color::Rgb(232, 72, 85);
 const YELLOW: Color = Color::Rgb(249, 220, 92);
@@ -54,6 +62,313 @@ fn write_not_allowed_hazards(
     Ok(())
 }
 
+fn format_unknown_hazards(unknown_hazards: &[UnknownHazard]) -> String {
+    unknown_hazards
+        .iter()
+        .map(|hazard| match hazard.suggestion {
+            Some(suggestion) => format!("{} (did you mean {suggestion}?)", hazard.name),
+            None => hazard.name.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn write_unknown_hazards(
+    stdout: &mut StandardStream,
+    unknown_hazards: &[UnknownHazard],
+) -> std::io::Result<()> {
+    if !unknown_hazards.is_empty() {
+        write_colored(
+            stdout,
+            RED,
+            16,
+            &format!(
+                "unknown hazards: {}",
+                format_unknown_hazards(unknown_hazards)
+            ),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_unknown_device_hazards(
+    stdout: &mut StandardStream,
+    unknown_device_hazards: &[UnknownDeviceHazards],
+) -> std::io::Result<()> {
+    if !unknown_device_hazards.is_empty() {
+        write_colored(stdout, RED, 0, "\nunknown hazards in device definitions:")?;
+        for device in unknown_device_hazards {
+            write_colored(
+                stdout,
+                GREY,
+                4,
+                &format!(
+                    "{}: {}",
+                    device.device,
+                    format_unknown_hazards(&device.hazards)
+                ),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_hazard_mismatches(
+    stdout: &mut StandardStream,
+    hazard_mismatches: &[HazardMismatch],
+) -> std::io::Result<()> {
+    if !hazard_mismatches.is_empty() {
+        write_colored(
+            stdout,
+            YELLOW,
+            0,
+            "\nmandatory hazards differing from their consts:",
+        )?;
+        for mismatch in hazard_mismatches {
+            write_colored(
+                stdout,
+                GREY,
+                4,
+                &format!(
+                    "{}::{}: checked [{}], const [{}]",
+                    mismatch.device,
+                    mismatch.action,
+                    mismatch.checked_hazards.join(", "),
+                    mismatch.const_hazards.join(", ")
+                ),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_device_catalog(
+    stdout: &mut StandardStream,
+    device_catalog: &[DeviceFileReport],
+) -> std::io::Result<()> {
+    if device_catalog.is_empty() {
+        return Ok(());
+    }
+
+    write_colored(stdout, Color::Ansi256(15), 0, "\ndevice catalog:")?;
+    for file in device_catalog {
+        write_colored(stdout, GREY, 4, &file.file.display().to_string())?;
+        if let Some(error) = file.error {
+            write_colored(stdout, RED, 8, &format!("cannot be read: {error}"))?;
+        }
+        for device in &file.devices {
+            let style = match device.style {
+                DeviceStyle::New => "new",
+                DeviceStyle::Methods => "methods",
+            };
+            let (color, outcome) = if device.recognized {
+                (CYAN, "")
+            } else {
+                (RED, ", not recognized")
+            };
+            write_colored(
+                stdout,
+                color,
+                8,
+                &format!("{} ({style}{outcome})", device.name),
+            )?;
+
+            if !device.missing_constants.is_empty() {
+                write_colored(
+                    stdout,
+                    RED,
+                    12,
+                    &format!("missing constants: {}", device.missing_constants.join(", ")),
+                )?;
+            }
+            if !device.missing_functions.is_empty() {
+                write_colored(
+                    stdout,
+                    RED,
+                    12,
+                    &format!("missing functions: {}", device.missing_functions.join(", ")),
+                )?;
+            }
+            for action in &device.unresolved_actions {
+                write_colored(
+                    stdout,
+                    YELLOW,
+                    12,
+                    &format!(
+                        "no hazards found for {}, missing {}",
+                        action.action, action.constant
+                    ),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_helpers(stdout: &mut StandardStream, helpers: &[HelperFunction]) -> std::io::Result<()> {
+    if !helpers.is_empty() {
+        write_colored(
+            stdout,
+            CYAN,
+            16,
+            &format!(
+                "resolved through: {}",
+                helpers
+                    .iter()
+                    .map(|helper| format!(
+                        "{} ({}: {}, {})",
+                        helper.name,
+                        helper.file.display(),
+                        helper.position.0,
+                        helper.position.1
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_route(stdout: &mut StandardStream, route: Option<&Route>) -> std::io::Result<()> {
+    if let Some(route) = route {
+        let description = route
+            .description
+            .map_or(String::new(), |description| format!(" ({description})"));
+        write_colored(
+            stdout,
+            CYAN,
+            16,
+            &format!("route: {} {}{description}", route.method, route.path),
+        )?;
+
+        if !route.inputs.is_empty() {
+            write_colored(
+                stdout,
+                CYAN,
+                16,
+                &format!(
+                    "inputs: {}",
+                    route
+                        .inputs
+                        .iter()
+                        .map(|input| format!("{} ({})", input.name, input.kind))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_handler(stdout: &mut StandardStream, handler: Option<&Handler>) -> std::io::Result<()> {
+    if let Some(handler) = handler {
+        let location = match (handler.file, handler.position) {
+            (Some(file), Some(position)) => {
+                format!(" ({}: {}, {})", file.display(), position.0, position.1)
+            }
+            _ => String::new(),
+        };
+        write_colored(
+            stdout,
+            CYAN,
+            16,
+            &format!("handler: {}{location}", handler.name),
+        )?;
+
+        for issue in &handler.issues {
+            let issue = match issue {
+                HandlerIssue::Missing => "handler not found in the crate",
+                HandlerIssue::NotAsync => "handler is not async",
+                HandlerIssue::SharedWithDifferentHazards => {
+                    "handler shared with actions carrying different hazards"
+                }
+            };
+            write_colored(stdout, RED, 16, issue)?;
+        }
+
+        for finding in &handler.findings {
+            let kind = match finding.kind {
+                FindingKind::Panic => "may panic",
+                FindingKind::BlockingCall => "blocking call in async handler",
+                FindingKind::UnsafeBlock => "unsafe block",
+            };
+            write_colored(
+                stdout,
+                YELLOW,
+                20,
+                &format!(
+                    "{kind}: {} in {} ({}: {}, {})",
+                    finding.construct,
+                    finding.function,
+                    finding.file.display(),
+                    finding.position.0,
+                    finding.position.1
+                ),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn format_actions(actions: &[ActionLocation]) -> String {
+    actions
+        .iter()
+        .map(|action| {
+            format!(
+                "{} ({}, {})",
+                action.name, action.position.0, action.position.1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn write_diagnostics(
+    stdout: &mut StandardStream,
+    diagnostics: &[Diagnostic],
+) -> std::io::Result<()> {
+    if !diagnostics.is_empty() {
+        write_colored(stdout, Color::Ansi256(15), 8, "diagnostics:")?;
+    }
+
+    for diagnostic in diagnostics {
+        let content = match diagnostic {
+            Diagnostic::RouteCollision {
+                method,
+                path,
+                actions,
+            } => format!(
+                "route collision on {method} {path}: {}",
+                format_actions(actions)
+            ),
+            Diagnostic::DuplicateHandler { handler, actions } => format!(
+                "handler {handler} bound to multiple actions: {}",
+                format_actions(actions)
+            ),
+            Diagnostic::MandatoryActionAsOptional { action, position } => format!(
+                "mandatory action {action} added as optional action ({}, {})",
+                position.0, position.1
+            ),
+            Diagnostic::RepeatedMandatoryAction { action, actions } => format!(
+                "mandatory action {action} called multiple times: {}",
+                format_actions(actions)
+            ),
+        };
+        write_colored(stdout, YELLOW, 12, &content)?;
+    }
+
+    Ok(())
+}
+
 fn write_colored(
     stdout: &mut StandardStream,
     color: Color,
@@ -65,12 +380,14 @@ fn write_colored(
     stdout.reset()
 }
 
-pub(crate) fn print_manifest(manifest: &[FileManifest]) -> std::io::Result<()> {
-    let mut stdout = StandardStream::stdout(ColorChoice::Always);
-
+fn write_manifest(stdout: &mut StandardStream, manifest: &Manifest) -> std::io::Result<()> {
     // Write files.
-    for file in manifest.iter().filter(|file| !file.devices.is_empty()) {
-        write_colored(&mut stdout, BLUE, 0, &format!("\n{}", file.file.display()))?;
+    for file in manifest
+        .files
+        .iter()
+        .filter(|file| !file.devices.is_empty())
+    {
+        write_colored(stdout, BLUE, 0, &format!("\n{}", file.file.display()))?;
 
         // Write devices.
         for device in &file.devices {
@@ -79,22 +396,34 @@ pub(crate) fn print_manifest(manifest: &[FileManifest]) -> std::io::Result<()> {
             stdout.reset()?;
             writeln!(stdout, "({}, {})", device.position.0, device.position.1)?;
 
-            write_colored(
-                &mut stdout,
-                Color::Ansi256(15),
-                8,
-                "defined mandatory actions:",
-            )?;
+            // Write device status.
+            match device.status {
+                DeviceStatus::NotBuilt => write_colored(stdout, RED, 8, "device never built")?,
+                DeviceStatus::NotServed => write_colored(stdout, RED, 8, "device never served")?,
+                DeviceStatus::ServedWithoutDiscovery => write_colored(
+                    stdout,
+                    YELLOW,
+                    8,
+                    "device served without a discovery service",
+                )?,
+                DeviceStatus::Served => {}
+            }
+
+            write_colored(stdout, Color::Ansi256(15), 8, "defined mandatory actions:")?;
 
             // Write defined mandatory actions.
             for action in &device.mandatory_actions {
-                write_colored(&mut stdout, GREY, 12, action.name)?;
-                write_hazards(&mut stdout, GREEN, &action.hazards)?;
-                write_not_allowed_hazards(&mut stdout, &action.not_allowed_hazards)?;
+                write_colored(stdout, GREY, 12, action.name)?;
+                write_route(stdout, action.route.as_ref())?;
+                write_handler(stdout, action.handler.as_ref())?;
+                write_hazards(stdout, GREEN, &action.hazards)?;
+                write_helpers(stdout, &action.resolved_through)?;
+                write_not_allowed_hazards(stdout, &action.not_allowed_hazards)?;
+                write_unknown_hazards(stdout, &action.unknown_hazards)?;
 
                 if !action.missing_hazards.is_empty() {
                     write_colored(
-                        &mut stdout,
+                        stdout,
                         RED,
                         16,
                         &format!(
@@ -114,7 +443,7 @@ pub(crate) fn print_manifest(manifest: &[FileManifest]) -> std::io::Result<()> {
             if let Some(missing_actions) = &device.missing_mandatory_actions {
                 if !missing_actions.is_empty() {
                     write_colored(
-                        &mut stdout,
+                        stdout,
                         RED,
                         12,
                         &format!(
@@ -126,13 +455,239 @@ pub(crate) fn print_manifest(manifest: &[FileManifest]) -> std::io::Result<()> {
             }
 
             // Write optional actions.
-            write_colored(&mut stdout, Color::Ansi256(15), 8, "optional actions:")?;
+            write_colored(stdout, Color::Ansi256(15), 8, "optional actions:")?;
             for action in &device.optional_actions {
-                write_colored(&mut stdout, GREY, 12, action.name)?;
-                write_hazards(&mut stdout, YELLOW, &action.hazards)?;
-                write_not_allowed_hazards(&mut stdout, &action.not_allowed_hazards)?;
+                write_colored(stdout, GREY, 12, action.name)?;
+                write_route(stdout, action.route.as_ref())?;
+                write_handler(stdout, action.handler.as_ref())?;
+                write_hazards(stdout, YELLOW, &action.hazards)?;
+                write_helpers(stdout, &action.resolved_through)?;
+                write_not_allowed_hazards(stdout, &action.not_allowed_hazards)?;
+                write_unknown_hazards(stdout, &action.unknown_hazards)?;
             }
+
+            // Write hazard categories.
+            if !device.hazard_categories.is_empty() {
+                write_colored(stdout, Color::Ansi256(15), 8, "hazard categories:")?;
+                for (category, hazards) in &device.hazard_categories {
+                    write_colored(
+                        stdout,
+                        GREY,
+                        12,
+                        &format!(
+                            "{category}: {}",
+                            hazards.iter().copied().collect::<Vec<_>>().join(", ")
+                        ),
+                    )?;
+                }
+            }
+
+            // Write HTTP method violations.
+            for violation in &device.method_violations {
+                write_colored(
+                    stdout,
+                    RED,
+                    8,
+                    &format!(
+                        "{} ({}, {}) exposes {} through {} {}",
+                        violation.action,
+                        violation.position.0,
+                        violation.position.1,
+                        violation.hazards.join(", "),
+                        violation.method,
+                        violation.path
+                    ),
+                )?;
+            }
+
+            // Write input violations.
+            for violation in &device.input_violations {
+                let reason = match &violation.kind {
+                    InputViolationKind::RangeOutOfBounds {
+                        minimum,
+                        maximum,
+                        allowed_minimum,
+                        allowed_maximum,
+                    } => format!(
+                        "range [{minimum}, {maximum}] exceeds the allowed range [{}, {}]",
+                        allowed_minimum.map_or("-inf".to_string(), |bound| bound.to_string()),
+                        allowed_maximum.map_or("inf".to_string(), |bound| bound.to_string())
+                    ),
+                    InputViolationKind::UnboundedInput { hazards } if hazards.is_empty() => {
+                        "unbounded input requires a range".to_string()
+                    }
+                    InputViolationKind::UnboundedInput { hazards } => {
+                        format!("unbounded input not allowed for {}", hazards.join(", "))
+                    }
+                };
+                write_colored(
+                    stdout,
+                    RED,
+                    8,
+                    &format!(
+                        "{} input {} ({}, {}): {reason}",
+                        violation.action,
+                        violation.input,
+                        violation.position.0,
+                        violation.position.1
+                    ),
+                )?;
+            }
+
+            // Write diagnostics.
+            write_diagnostics(stdout, &device.diagnostics)?;
         }
     }
+
+    // Write feature combinations.
+    if !manifest.feature_combinations.is_empty() {
+        write_colored(stdout, Color::Ansi256(15), 0, "\nfeature combinations:")?;
+        for combination in &manifest.feature_combinations {
+            let (color, verdict) = if combination.compliant {
+                (GREEN, "compliant")
+            } else {
+                (RED, "not compliant")
+            };
+            write_colored(
+                stdout,
+                color,
+                4,
+                &format!("[{}] {verdict}", combination.features.join(", ")),
+            )?;
+            for device in &combination.non_compliant_devices {
+                write_colored(
+                    stdout,
+                    GREY,
+                    8,
+                    &format!(
+                        "{} ({}: {}, {})",
+                        device.name,
+                        device.file.display(),
+                        device.position.0,
+                        device.position.1
+                    ),
+                )?;
+            }
+        }
+    }
+
+    // Write hazard descriptions.
+    if !manifest.hazards.is_empty() {
+        write_colored(stdout, Color::Ansi256(15), 0, "\nhazards:")?;
+        for hazard in &manifest.hazards {
+            let mut content = hazard.name.to_string();
+            if let Some(id) = hazard.info.id {
+                content.push_str(&format!(" ({id})"));
+            }
+            if let Some(category) = &hazard.info.category {
+                content.push_str(&format!(" [{category}]"));
+            }
+            if let Some(description) = &hazard.info.description {
+                content.push_str(&format!(": {description}"));
+            }
+            write_colored(stdout, GREY, 4, &content)?;
+        }
+    }
+
+    // Write unreachable files.
+    if !manifest.unreachable_files.is_empty() {
+        write_colored(stdout, YELLOW, 0, "\nunreachable files:")?;
+        for path in manifest.unreachable_files {
+            write_colored(stdout, GREY, 4, &path.display().to_string())?;
+        }
+    }
+
+    // Write unknown hazards of the device definitions.
+    write_unknown_device_hazards(stdout, manifest.unknown_device_hazards)?;
+
+    // Write mismatching mandatory hazards of the device definitions.
+    write_hazard_mismatches(stdout, manifest.hazard_mismatches)?;
+
+    // Write the device catalog.
+    write_device_catalog(stdout, manifest.device_catalog)
+}
+
+pub(crate) fn print_warning(content: &str) -> std::io::Result<()> {
+    let mut stderr = StandardStream::stderr(ColorChoice::Always);
+    write_colored(&mut stderr, YELLOW, 0, &format!("warning: {content}"))
+}
+
+pub(crate) fn print_manifest(manifest: &Manifest) -> std::io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+    write_manifest(&mut stdout, manifest)
+}
+
+pub(crate) fn print_workspace_manifest(manifest: &WorkspaceManifest) -> std::io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+
+    // Write packages.
+    for package in &manifest.packages {
+        let (color, verdict) = if package.compliant {
+            (GREEN, "compliant")
+        } else {
+            (RED, "not compliant")
+        };
+        write_colored(
+            &mut stdout,
+            color,
+            0,
+            &format!("\n[{}] {verdict}", package.name),
+        )?;
+        write_manifest(&mut stdout, &package.manifest)?;
+    }
+
+    // Write unknown hazards of the device definitions.
+    write_unknown_device_hazards(&mut stdout, manifest.unknown_device_hazards)?;
+
+    // Write mismatching mandatory hazards of the device definitions.
+    write_hazard_mismatches(&mut stdout, manifest.hazard_mismatches)?;
+
+    // Write the device catalog.
+    write_device_catalog(&mut stdout, manifest.device_catalog)
+}
+
+pub(crate) fn print_lint_manifest(manifest: &LintManifest) -> std::io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+
+    if manifest.consistent {
+        return write_colored(&mut stdout, GREEN, 0, "device definitions are consistent");
+    }
+
+    // Write the inconsistencies of each device file.
+    for file in manifest.files {
+        write_colored(&mut stdout, BLUE, 0, &format!("\n{}", file.file.display()))?;
+        for issue in &file.issues {
+            let (color, content) = match issue {
+                LintIssue::MissingFunction { device, function } => {
+                    (RED, format!("{device}: missing function {function}"))
+                }
+                LintIssue::MissingConst { device, constant } => {
+                    (RED, format!("{device}: missing const {constant}"))
+                }
+                LintIssue::HazardNotAllowed {
+                    device,
+                    action,
+                    hazard,
+                } => (
+                    RED,
+                    format!("{device}: {hazard} hazard of {action} is not allowed"),
+                ),
+                LintIssue::ActionNotInserted { device, variant } => (
+                    RED,
+                    format!("{device}: Actions::{variant} is not inserted inside new()"),
+                ),
+                LintIssue::SharedConst { constant, devices } => (
+                    YELLOW,
+                    format!("{constant} is shared by {}", devices.join(", ")),
+                ),
+                LintIssue::UnreadableFile { error } => (RED, format!("cannot be read: {error}")),
+                LintIssue::UnusedConst { constant } => {
+                    (YELLOW, format!("unused hazard const {constant}"))
+                }
+            };
+            write_colored(&mut stdout, color, 4, &content)?;
+        }
+    }
+
     Ok(())
 }
@@ -1,21 +1,24 @@
+use std::collections::HashSet;
+
 use regex::Regex;
 use regex_static::once_cell::sync::Lazy;
 
 // Matches an hazard declared in the `Hazard::HazardName` form.
 pub(crate) static HAZARD_RE: Lazy<Regex> = regex_static::lazy_regex!(r"Hazard::(\w+)");
 
-// Matches the arguments (content between round brackests) of a function call.
-//
-// Given for example `DeviceAction::with_hazards(toggle_config, toggle, &[Hazard::FireHazard, Hazard::PowerSurge])`
-// it will match `toggle_config, toggle, &[Hazard::FireHazard, Hazard::PowerSurge]`.
-pub(crate) static ARGS_RE: Lazy<Regex> = regex_static::lazy_regex!(r"\((?s)(.*?)\)");
+// Matches a `#[path = "file.rs"]` attribute, capturing the path.
+pub(crate) static PATH_ATTRIBUTE_RE: Lazy<Regex> =
+    regex_static::lazy_regex!(r#"^#\[\s*path\s*=\s*"([^"]+)"\s*\]$"#);
 
-// Regex that that matches a method invokation
-// that has one of the following forms:
+// Returns all the hazards contained in a piece of code.
+// It will for example retrieve "FireHazard" and "PowerSurge" from:
 //
-// - `expression(content).`
-// - `expression(content)?.`
-#[inline(always)]
-pub(crate) fn method_re(expression: &str) -> Option<Regex> {
-    Regex::new(&format!(r"(?s){}\((.*?)\)\s*\??\s*\.", expression)).ok()
+// add_action(DeviceAction::with_hazards(toggle_config, toggle, &[Hazard::FireHazard, Hazard::PowerSurge]))
+pub(crate) fn hazards_from_text(text: &str) -> Option<HashSet<&str>> {
+    let hazards = HAZARD_RE
+        .captures_iter(text)
+        .filter_map(|capture| capture.get(1).map(|re_match| re_match.as_str()))
+        .collect::<HashSet<_>>();
+
+    Some(hazards)
 }
@@ -0,0 +1,98 @@
+// Imports and const definitions...
+
+// Hazards shared by the mandatory actions.
+const BASE_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption];
+
+// Mandatory actions hazards.
+const TURN_ON: &[Hazard] = &[..BASE_HAZARDS, Hazard::FireHazard];
+const TURN_OFF: &[Hazard] = BASE_HAZARDS;
+const FIRST_LOOP: &[Hazard] = SECOND_LOOP;
+const SECOND_LOOP: &[Hazard] = &[..FIRST_LOOP, Hazard::PowerOutage];
+
+// Hazards allowed by other devices.
+const EXTRA_ALLOWED_HAZARDS: &[Hazard] = &[Hazard::SpoiledFood];
+
+// Allowed hazards.
+const ALLOWED_HAZARDS: &[Hazard] = &[..TURN_ON, ..SECOND_LOOP];
+
+pub struct ComposedDevice<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Main server route for device routes.
+    main_route: &'static str,
+    // Device state.
+    state: Option<S>,
+    // Device.
+    device: Device<S>,
+    // Allowed device hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> ComposedDevice<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`ComposedDevice`] instance.
+    pub fn new<H, T, H1, T1>(
+        turn_on: DeviceAction<H, T>,
+        turn_off: DeviceAction<H1, T1>,
+        on: DeviceAction<H1, T1>,
+        first_loop: DeviceAction<H1, T1>,
+    ) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+        H1: Handler<T1, ()>,
+        T1: 'static,
+    {
+        // Raise an error whether turn_on does not contain
+        // fire or electric energy consumption hazards.
+        if turn_on.miss_hazards(TURN_ON) {
+            return Err(Error::new(
+                ErrorKind::ComposedDevice,
+                "No fire or electric energy consumption hazards for the `turn_on` route",
+            ));
+        }
+
+        // Raise an error whether turn_off does not contain
+        // an electric energy consumption hazard.
+        if turn_off.miss_hazards(TURN_OFF) {
+            return Err(Error::new(
+                ErrorKind::ComposedDevice,
+                "No electric energy consumption hazard for the `turn_off` route",
+            ));
+        }
+
+        // Raise an error whether first_loop does not contain
+        // a power outage hazard.
+        if first_loop.miss_hazards(FIRST_LOOP) {
+            return Err(Error::new(
+                ErrorKind::ComposedDevice,
+                "No power outage hazard for the `first_loop` route",
+            ));
+        }
+
+        // Create a new device.
+        let device = Device::new(DeviceKind::ComposedDevice)
+            .add_action(turn_on)
+            .add_action(turn_off)
+            .add_action(on)
+            .add_action(first_loop);
+
+        Ok(Self {
+            main_route: COMPOSED_DEVICE_MAIN_ROUTE,
+            device,
+            state: None,
+            allowed_hazards: ALLOWED_HAZARDS,
+        })
+    }
+
+    /// Main route setting...
+
+    /// Adds an additional action...
+
+    /// Device state setting...
+
+    /// Build...
+}
@@ -0,0 +1,130 @@
+// Imports and const definitions...
+
+// Mandatory actions hazards.
+const TURN_ON: Hazard = Hazard::FireHazard;
+const OFF_HAZARD: Hazard = Hazard::ElectricEnergyConsumption;
+const SET_TIMER: &[Hazard] = &[Hazard::PowerOutage];
+
+// Allowed hazards.
+const ALLOWED_HAZARDS: &[Hazard] = &[
+    Hazard::ElectricEnergyConsumption,
+    Hazard::FireHazard,
+    Hazard::PowerOutage,
+];
+
+// Mandatory oven actions.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Actions {
+    TurnOn,
+    TurnOff,
+    SetTimer,
+}
+
+pub struct Oven<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Main server route for oven routes.
+    main_route: &'static str,
+    // Oven state.
+    state: Option<S>,
+    // Device.
+    device: Device<S>,
+    // Mandatory oven actions.
+    mandatory_actions: FnvIndexSet<Actions, MAXIMUM_ELEMENTS>,
+    // Allowed oven hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> Oven<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`Oven`] instance.
+    pub fn new() -> Self {
+        // Create a new device.
+        let device = Device::new(DeviceKind::Oven);
+
+        // Define mandatory actions.
+        let mut mandatory_actions = FnvIndexSet::new();
+        let _ = mandatory_actions.insert(Actions::TurnOn);
+        let _ = mandatory_actions.insert(Actions::TurnOff);
+        let _ = mandatory_actions.insert(Actions::SetTimer);
+
+        Self {
+            main_route: OVEN_MAIN_ROUTE,
+            device,
+            state: None,
+            mandatory_actions,
+            allowed_hazards: ALLOWED_HAZARDS,
+        }
+    }
+
+    /// Main route setting...
+
+    /// Adds turn on action for an [`Oven`].
+    pub fn turn_on<H, T>(mut self, turn_on: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether turn_on does not contain
+        // fire or electric energy consumption hazards.
+        if turn_on.miss_hazards(&[Hazard::FireHazard, Hazard::ElectricEnergyConsumption]) {
+            return Err(Error::new(
+                ErrorKind::Oven,
+                "No fire or electric energy consumption hazards for the `turn_on` route",
+            ));
+        }
+
+        self.device = self.device.add_action(turn_on);
+
+        // Remove turn_on action from the list of actions to set.
+        self.mandatory_actions.remove(&Actions::TurnOn);
+
+        Ok(self)
+    }
+
+    /// Adds turn off action for an [`Oven`].
+    pub fn turn_off<H, T>(mut self, action: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether turn_off does not contain
+        // electric energy consumption hazard.
+        if action.miss_hazard(OFF_HAZARD) {
+            return Err(Error::new(
+                ErrorKind::Oven,
+                "No electric energy consumption hazard for the `turn_off` route",
+            ));
+        }
+
+        self.device = self.device.add_action(action);
+
+        // Remove turn_off action from the list of actions to set.
+        self.mandatory_actions.remove(&Actions::TurnOff);
+
+        Ok(self)
+    }
+
+    /// Adds set timer action for an [`Oven`].
+    pub fn set_timer<H, T>(mut self, set_timer: DeviceAction<H, T>) -> Self
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        self.device = self.device.add_action(set_timer);
+
+        // Remove set_timer action from the list of actions to set.
+        self.mandatory_actions.remove(&Actions::SetTimer);
+
+        self
+    }
+
+    /// Adds an additional action...
+
+    /// Device state setting...
+
+    /// Build...
+}
@@ -0,0 +1,136 @@
+use ascot_library::device::DeviceKind;
+use ascot_library::hazards::Hazard;
+
+use axum::handler::Handler;
+use heapless::FnvIndexSet;
+
+use crate::device::{Device, DeviceAction};
+use crate::error::{Error, ErrorKind, Result};
+use crate::MAXIMUM_ELEMENTS;
+
+// The default main route for a heater.
+const HEATER_MAIN_ROUTE: &str = "/heater";
+
+// Mandatory actions hazards.
+const TURN_ON: &[Hazard] = &[Hazard::FireHazard, Hazard::ElectricEnergyConsumption];
+const SET_TEMPERATURE: Hazard = Hazard::ElectricEnergyConsumption;
+
+// Hazards of a removed action.
+const TURN_OFF_HAZARDS: &[Hazard] = &[Hazard::PowerOutage];
+
+// Allowed hazards.
+const ALLOWED_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption];
+
+// Mandatory heater actions.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Actions {
+    TurnOn,
+    SetTemperature,
+    TurnOff,
+}
+
+/// A smart home heater.
+pub struct Heater<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Main server route for heater routes.
+    main_route: &'static str,
+    // Heater state.
+    state: Option<S>,
+    // Device.
+    device: Device<S>,
+    // Mandatory heater actions.
+    mandatory_actions: FnvIndexSet<Actions, MAXIMUM_ELEMENTS>,
+    // Allowed heater hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> Heater<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`Heater`] instance.
+    pub fn new() -> Self {
+        // Create a new device.
+        let device = Device::new(DeviceKind::Heater);
+
+        // Define mandatory actions.
+        let mut mandatory_actions = FnvIndexSet::new();
+        let _ = mandatory_actions.insert(Actions::TurnOn);
+        let _ = mandatory_actions.insert(Actions::SetTemperature);
+
+        Self {
+            main_route: HEATER_MAIN_ROUTE,
+            device,
+            state: None,
+            mandatory_actions,
+            allowed_hazards: ALLOWED_HAZARDS,
+        }
+    }
+
+    /// Adds turn on action for a [`Heater`].
+    pub fn turn_on<H, T>(mut self, turn_on: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether turn_on does not contain
+        // fire or electric energy consumption hazards.
+        if turn_on.miss_hazards(TURN_ON) {
+            return Err(Error::new(
+                ErrorKind::Heater,
+                "No fire or electric energy consumption hazards for the `turn_on` route",
+            ));
+        }
+
+        self.device = self.device.add_action(turn_on);
+
+        // Remove turn_on action from the list of actions to set.
+        self.mandatory_actions.remove(&Actions::TurnOn);
+
+        Ok(self)
+    }
+
+    /// Adds set temperature action for a [`Heater`].
+    pub fn set_temperature<H, T>(mut self, set_temperature: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether set_temperature does not contain
+        // electric energy consumption hazard.
+        if set_temperature.miss_hazard(SET_TEMPERATURE) {
+            return Err(Error::new(
+                ErrorKind::Heater,
+                "No electric energy consumption hazard for the `set_temperature` route",
+            ));
+        }
+
+        self.device = self.device.add_action(set_temperature);
+
+        // Remove set_temperature action from the list of actions to set.
+        self.mandatory_actions.remove(&Actions::SetTemperature);
+
+        Ok(self)
+    }
+
+    /// Builds a new [`Device`].
+    pub fn build(self) -> Result<Device<S>> {
+        // Return an error if not all mandatory actions are set.
+        if !self.mandatory_actions.is_empty() {
+            return Err(Error::new(
+                ErrorKind::Heater,
+                format!(
+                    "The following mandatory actions are not set: {:?}",
+                    self.mandatory_actions
+                ),
+            ));
+        };
+
+        let mut device = self.device.main_route(self.main_route).finalize();
+        device.state = self.state;
+
+        Ok(device)
+    }
+}
@@ -0,0 +1,4 @@
+// Devices whose module files are partly missing.
+
+mod light;
+mod removed;
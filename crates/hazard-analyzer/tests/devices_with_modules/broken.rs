@@ -0,0 +1,4 @@
+// Device whose definition has been removed...
+
+// Allowed hazards.
+const ALLOWED_HAZARDS: &[Hazard] = &[Hazard::FireHazard];
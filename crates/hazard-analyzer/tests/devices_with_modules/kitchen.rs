@@ -0,0 +1,3 @@
+// Kitchen devices.
+
+mod fridge;
@@ -0,0 +1,5 @@
+// Devices organized in nested modules.
+
+mod broken;
+mod kitchen;
+mod light;
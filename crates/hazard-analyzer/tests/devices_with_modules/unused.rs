@@ -0,0 +1,6 @@
+// Device file not declared inside the devices module.
+
+pub struct Unused<S> {
+    // Device.
+    device: Device<S>,
+}
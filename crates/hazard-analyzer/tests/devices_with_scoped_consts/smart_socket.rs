@@ -0,0 +1,83 @@
+// Imports and const definitions...
+
+// Mandatory actions hazards.
+const CHARGE: Hazard = Hazard::ElectricEnergyConsumption;
+
+// Allowed hazards of the devices which do not define their own.
+const ALLOWED_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption];
+
+pub struct Socket<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Device.
+    device: Device<S>,
+    // Allowed socket hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> Socket<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Mandatory actions hazards.
+    const TURN_ON: Hazard = Hazard::FireHazard;
+
+    // Allowed hazards.
+    const ALLOWED_HAZARDS: &'static [Hazard] =
+        &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard];
+
+    /// Creates a new [`Socket`] instance.
+    pub fn new<H, T>(turn_on: DeviceAction<H, T>) -> Self
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Create a new device.
+        let device = Device::new(DeviceKind::Socket).add_action(turn_on);
+
+        Self {
+            device,
+            allowed_hazards: Self::ALLOWED_HAZARDS,
+        }
+    }
+}
+
+pub struct Charger<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Device.
+    device: Device<S>,
+    // Allowed charger hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> Charger<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`Charger`] instance.
+    pub fn new<H, T>(charge: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether charge does not contain
+        // an electric energy consumption hazard.
+        if charge.miss_hazard(CHARGE) {
+            return Err(Error::new(
+                ErrorKind::Charger,
+                "No electric energy consumption hazard for the `charge` route",
+            ));
+        }
+
+        // Create a new device.
+        let device = Device::new(DeviceKind::Charger).add_action(charge);
+
+        Ok(Self {
+            device,
+            allowed_hazards: ALLOWED_HAZARDS,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+// Imports and const definitions...
+
+// Mandatory actions hazards.
+const BOIL: Hazard = Hazard::FireHazard;
+
+// Allowed hazards.
+const ALLOWED_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard];
+
+// Mandatory kettle actions.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Actions {
+    Boil,
+}
+
+pub struct Kettle<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Device.
+    device: Device<S>,
+    // Mandatory kettle actions.
+    mandatory_actions: FnvIndexSet<Actions, MAXIMUM_ELEMENTS>,
+    // Allowed kettle hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> Kettle<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`Kettle`] instance.
+    pub fn new() -> Self {
+        // Create a new device.
+        let device = Device::new(DeviceKind::Kettle);
+
+        // Define mandatory actions.
+        let mut mandatory_actions = FnvIndexSet::new();
+        let _ = mandatory_actions.insert(Actions::Boil);
+
+        Self {
+            device,
+            mandatory_actions,
+            allowed_hazards: ALLOWED_HAZARDS,
+        }
+    }
+
+    /// Adds boil action for a [`Kettle`].
+    pub fn boil<H, T>(mut self, boil: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether boil does not contain
+        // fire or electric energy consumption hazards.
+        if boil
+            .miss_hazards(&[Hazard::FireHazard, Hazard::ElectricEnergyConsumption])
+        {
+            return Err(Error::new(
+                ErrorKind::Kettle,
+                "No fire or electric energy consumption hazards for the `boil` route",
+            ));
+        }
+
+        self.device = self.device.add_action(boil);
+
+        // Remove boil action from the list of actions to set.
+        self.mandatory_actions.remove(&Actions::Boil);
+
+        Ok(self)
+    }
+}
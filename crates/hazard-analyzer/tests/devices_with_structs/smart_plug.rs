@@ -0,0 +1,118 @@
+// Imports and const definitions...
+
+// Mandatory actions hazards.
+const TURN_ON: Hazard = Hazard::FireHazard;
+const TOGGLE: &[Hazard] = &[Hazard::ElectricEnergyConsumption, Hazard::PowerOutage];
+
+// Allowed hazards.
+const ALLOWED_HAZARDS: &[Hazard] = &[
+    Hazard::ElectricEnergyConsumption,
+    Hazard::FireHazard,
+    Hazard::PowerOutage,
+];
+
+// Plug configuration, which is not a device.
+pub struct PlugConfig {
+    // Maximum power in watts.
+    max_power: u32,
+}
+
+pub struct Plug<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Main server route for plug routes.
+    main_route: &'static str,
+    // Plug state.
+    state: Option<S>,
+    // Device.
+    device: Device<S>,
+    // Allowed plug hazards.
+    allowed_hazards: &'static [Hazard],
+}
+
+impl<S> Plug<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`Plug`] instance.
+    pub fn new<H, T, H1, T1>(
+        turn_on: DeviceAction<H, T>,
+        turn_off: DeviceAction<H1, T1>,
+    ) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+        H1: Handler<T1, ()>,
+        T1: 'static,
+    {
+        // Raise an error whether turn_on does not contain a fire hazard.
+        if turn_on.miss_hazard(TURN_ON) {
+            return Err(Error::new(
+                ErrorKind::Plug,
+                "No fire hazard for the `turn_on` route",
+            ));
+        }
+
+        // Create a new device.
+        let device = Device::new(DeviceKind::Plug)
+            .add_action(turn_on)
+            .add_action(turn_off);
+
+        Ok(Self {
+            main_route: PLUG_MAIN_ROUTE,
+            device,
+            state: None,
+            allowed_hazards: ALLOWED_HAZARDS,
+        })
+    }
+
+    /// Main route setting...
+
+    /// Adds an additional action...
+
+    /// Build...
+}
+
+pub struct PowerStrip<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // Main server route for power strip routes.
+    main_route: &'static str,
+    // Plugs of the power strip.
+    plugs: Vec<Plug<S>>,
+}
+
+impl<S> PowerStrip<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates a new [`PowerStrip`] instance.
+    pub fn new<H, T>(toggle: DeviceAction<H, T>) -> Result<Self>
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        // Raise an error whether toggle does not contain
+        // electric energy consumption or power outage hazards.
+        if toggle.miss_hazards(TOGGLE) {
+            return Err(Error::new(
+                ErrorKind::PowerStrip,
+                "No electric energy consumption or power outage hazards for the `toggle` route",
+            ));
+        }
+
+        // Create a new device.
+        let device = Device::new(DeviceKind::PowerStrip).add_action(toggle);
+
+        Ok(Self {
+            main_route: POWER_STRIP_MAIN_ROUTE,
+            plugs: Vec::new(),
+        })
+    }
+
+    /// Main route setting...
+
+    /// Build...
+}
@@ -0,0 +1,2 @@
+#[path = "../hw/fridge_impl.rs"]
+pub(crate) mod fridge;
@@ -0,0 +1,10 @@
+use ascot_axum::devices::fridge::Fridge;
+
+pub(crate) fn firmware() {
+
+    let fridge = Fridge::new()
+        .increase_temperature(DeviceAction::with_hazards(increase_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?
+        .decrease_temperature(DeviceAction::with_hazard(decrease_config, decrease_temperature, Hazard::ElectricEnergyConsumption))?
+        .build()?;
+
+}
@@ -0,0 +1,7 @@
+mod devices;
+mod light;
+
+fn main() {
+    light::firmware();
+    devices::fridge::firmware();
+}
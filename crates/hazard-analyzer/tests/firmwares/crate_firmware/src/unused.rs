@@ -0,0 +1,12 @@
+use ascot_axum::devices::light::Light;
+
+// This module is not declared by any target, so it is not analyzed.
+fn firmware() {
+
+    let light = Light::new(
+        DeviceAction::no_hazards(light_on_config, turn_light_on),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+}
@@ -0,0 +1,15 @@
+use ascot_axum::devices::light::Light;
+
+pub(crate) async fn firmware() -> Result<(), Error> {
+
+    let light = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+    AscotServer::new(light)
+        .run_service(ServiceBuilder::new("mdns-sd"))?
+        .run()
+        .await
+}
@@ -0,0 +1,8 @@
+mod light;
+
+#[cfg(feature = "fridge")]
+mod fridge;
+
+async fn main() -> Result<(), Error> {
+    light::firmware().await
+}
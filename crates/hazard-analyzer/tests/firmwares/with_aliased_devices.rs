@@ -0,0 +1,33 @@
+use ascot_axum::devices::fridge::*;
+use ascot_axum::devices::light::Light as Lamp;
+
+struct MyLight;
+
+impl MyLight {
+    fn new(first: u8, second: u8) -> Self {
+        Self
+    }
+}
+
+fn firmware() {
+
+    let lamp = Lamp::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+    let light = ascot_axum::devices::light::Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+    let my_light = MyLight::new(0, 1);
+
+    let fridge = Fridge::new()
+        .increase_temperature(DeviceAction::with_hazards(increase_temp_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?
+        .decrease_temperature(DeviceAction::with_hazard(decrease_temp_config, decrease_temperature, Hazard::ElectricEnergyConsumption))?
+        .build()?;
+
+}
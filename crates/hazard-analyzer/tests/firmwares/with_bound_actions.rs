@@ -0,0 +1,14 @@
+fn firmware() {
+
+    let first = DeviceAction::with_hazard(first_action_config, first_action, Hazard::FireHazard);
+    let second_hazards = &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard];
+    let second = DeviceAction::with_hazards(second_action_config, second_action, second_hazards);
+    let third = DeviceAction::no_hazards(third_action_config, third_action);
+    let optional = DeviceAction::no_hazards(optional_action_config, optional_action);
+
+    let device = NewDevice::new(first, second, third)?
+        .add_action(optional)?
+        .state(device_state)
+        .build();
+
+}
@@ -0,0 +1,13 @@
+fn firmware() {
+
+    let device = MethodsDevice::new()
+        // .add_action(DeviceAction::with_hazard(commented_config, commented_action, Hazard::PowerSurge))?
+        .first_action(DeviceAction::with_hazard(Route::put("/first(").description("First action."), first_action, Hazard::PowerOutage))?
+        .second_action(DeviceAction::with_hazards(second_action_config, second_action, &[Hazard::FireHazard, Hazard::SpoiledFood]))?
+        /* .third_action(DeviceAction::with_hazard(third_action_config, third_action, Hazard::PowerSurge)) */
+        .third_action(DeviceAction::no_hazards(third_action_config, third_action))
+        .add_action(DeviceAction::no_hazards(optional_action_config, optional_action))?
+        .state(device_state)
+        .build()?;
+
+}
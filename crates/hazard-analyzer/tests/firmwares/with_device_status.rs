@@ -0,0 +1,39 @@
+use ascot_axum::server::AscotServer as Server;
+
+async fn main() -> Result<(), Error> {
+
+    // A light which is never built.
+    let unbuilt_light = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?;
+
+    // A light which is built, but never served.
+    let unserved_light = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+    // A light which is served without a discovery service.
+    let light = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+    let server = Server::new(light);
+    server.run().await?;
+
+    // A light which is served along with a discovery service.
+    Server::new(
+        Light::new(
+            DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+            DeviceAction::no_hazards(light_off_config, turn_light_off),
+        )?
+        .build(),
+    )
+    .run_service(ServiceBuilder::new("mdns-sd"))?
+    .run()
+    .await
+}
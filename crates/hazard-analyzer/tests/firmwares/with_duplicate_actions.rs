@@ -0,0 +1,17 @@
+fn firmware() {
+
+    let light_on_config = Route::put("/on").description("Turn light on.");
+    let light_off_config = Route::put("/off").description("Turn light off.");
+    let toggle_config = Route::put("/on").description("Toggle a light.");
+
+    let device = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .add_action(DeviceAction::no_hazards(toggle_config, toggle))?
+    .add_action(DeviceAction::no_hazards(Route::post("/toggle"), toggle))?
+    .add_action(DeviceAction::with_hazard(Route::post("/on"), turn_light_on, Hazard::FireHazard))?
+    .state(device_state)
+    .build();
+
+}
@@ -0,0 +1,39 @@
+use std::thread;
+
+async fn turn_light_on() -> Result<DevicePayload, DeviceError> {
+    let brightness = read_brightness();
+    thread::sleep(Duration::from_millis(brightness));
+    Ok(DevicePayload::empty())
+}
+
+async fn turn_light_off() -> Result<DevicePayload, DeviceError> {
+    unsafe {
+        LIGHT_ON = false;
+    }
+    Ok(DevicePayload::empty())
+}
+
+fn toggle() -> Result<DevicePayload, DeviceError> {
+    thread::sleep(Duration::from_millis(read_brightness()));
+    Ok(DevicePayload::empty())
+}
+
+fn read_brightness() -> u64 {
+    let value = std::fs::read_to_string("/sys/brightness").unwrap();
+    if value.is_empty() {
+        panic!("Missing brightness");
+    }
+    value.trim().parse().expect("Invalid brightness")
+}
+
+fn firmware() {
+
+    let device = Light::new(
+        DeviceAction::with_hazard(Route::put("/on"), turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(Route::put("/off"), turn_light_off),
+    )?
+    .add_action(DeviceAction::no_hazards(Route::put("/toggle"), toggle))?
+    .state(device_state)
+    .build();
+
+}
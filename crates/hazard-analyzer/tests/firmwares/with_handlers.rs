@@ -0,0 +1,25 @@
+fn turn_light_on() -> Result<DevicePayload, DeviceError> {
+    Ok(DevicePayload::empty())
+}
+
+async fn turn_light_off() -> Result<DevicePayload, DeviceError> {
+    Ok(DevicePayload::empty())
+}
+
+async fn toggle() -> Result<DevicePayload, DeviceError> {
+    Ok(DevicePayload::empty())
+}
+
+fn firmware() {
+
+    let device = Light::new(
+        DeviceAction::with_hazard(Route::put("/on"), turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(Route::put("/off"), turn_light_off),
+    )?
+    .add_action(DeviceAction::no_hazards(Route::put("/toggle"), toggle))?
+    .add_action(DeviceAction::with_hazard(Route::post("/toggle"), toggle, Hazard::ElectricEnergyConsumption))?
+    .add_action(DeviceAction::no_hazards(Route::put("/energy"), handlers::save_energy))?
+    .state(device_state)
+    .build();
+
+}
@@ -0,0 +1,9 @@
+fn firmware() {
+
+    let device = Oven::new()
+        .turn_on(DeviceAction::with_hazards(turn_on_config, turn_on, &[Hazard::FireHazard, Hazard::ElectricEnergyConsumption]))?
+        .turn_off(DeviceAction::with_hazard(turn_off_config, turn_off, Hazard::ElectricEnergyConsumption))?
+        .set_timer(DeviceAction::with_hazard(set_timer_config, set_timer, Hazard::PowerOutage))
+        .build()?;
+
+}
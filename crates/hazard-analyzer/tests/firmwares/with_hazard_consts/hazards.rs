@@ -0,0 +1,3 @@
+pub(crate) const SECOND_ACTION_HAZARDS: &[Hazard] = &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard];
+
+pub(crate) static OPTIONAL_ACTION_HAZARDS: [Hazard; 1] = [Hazard::ElectricEnergyConsumption];
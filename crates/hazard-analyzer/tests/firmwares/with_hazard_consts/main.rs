@@ -0,0 +1,18 @@
+mod hazards;
+
+use hazards::SECOND_ACTION_HAZARDS as SECOND_HAZARDS;
+
+const FIRST_ACTION_HAZARD: Hazard = Hazard::FireHazard;
+
+fn firmware() {
+
+    let device = NewDevice::new(
+        DeviceAction::with_hazard(first_action_config, first_action, FIRST_ACTION_HAZARD),
+        DeviceAction::with_hazards(second_action_config, second_action, SECOND_HAZARDS),
+        DeviceAction::no_hazards(third_action_config, third_action)
+    )?
+    .add_action(DeviceAction::with_hazards(optional_action_config, optional_action, &hazards::OPTIONAL_ACTION_HAZARDS))?
+    .state(device_state)
+    .build();
+
+}
@@ -0,0 +1,17 @@
+use ascot_library::hazards::Hazard;
+
+pub(crate) fn second_device_action() -> DeviceAction {
+    return DeviceAction::with_hazards(
+        second_action_config,
+        second_action,
+        &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard],
+    );
+}
+
+pub(crate) fn third_device_action() -> DeviceAction {
+    no_hazards_action()
+}
+
+fn no_hazards_action() -> DeviceAction {
+    DeviceAction::no_hazards(third_action_config, third_action)
+}
@@ -0,0 +1,21 @@
+mod actions;
+
+use actions::{second_device_action, third_device_action};
+
+fn firmware() {
+
+    let device = NewDevice::new(first_device_action(), second_device_action(), third_device_action())?
+        .add_action(optional_device_action())?
+        .state(device_state)
+        .build();
+
+}
+
+fn first_device_action() -> DeviceAction {
+    let hazard = Hazard::FireHazard;
+    DeviceAction::with_hazard(first_action_config, first_action, hazard)
+}
+
+fn optional_device_action() -> DeviceAction {
+    DeviceAction::no_hazards(optional_action_config, optional_action)
+}
@@ -0,0 +1,20 @@
+use ascot_axum::devices::light::Light;
+
+pub(crate) fn kitchen_light() -> Result<Device<()>, Error> {
+    let light = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+    Ok(light)
+}
+
+pub(crate) fn garden_light() -> Device<()> {
+    Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )
+    .unwrap()
+    .build()
+}
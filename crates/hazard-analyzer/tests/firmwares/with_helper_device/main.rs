@@ -0,0 +1,18 @@
+mod lights;
+
+use ascot_axum::server::AscotServer;
+use lights::{garden_light, kitchen_light};
+
+async fn main() -> Result<(), Error> {
+
+    // A light built by a helper, which is never served.
+    let _garden_light = garden_light();
+
+    // A light built by a helper and served along with a discovery service.
+    let light = kitchen_light()?;
+
+    AscotServer::new(light)
+        .run_service(ServiceBuilder::new("mdns-sd"))?
+        .run()
+        .await
+}
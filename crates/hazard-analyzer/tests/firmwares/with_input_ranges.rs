@@ -0,0 +1,14 @@
+fn firmware() {
+
+    let increase_config = Route::put("/increase-temperature")
+        .input(Input::rangef64("increment", (1., 8., 0.5, 2.)));
+    let decrease_config = Route::put("/decrease-temperature")
+        .input(Input::rangef64("decrement", (1., 4., 0.1, 2.)));
+
+    let fridge = Fridge::new()
+        .increase_temperature(DeviceAction::with_hazards(increase_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?
+        .decrease_temperature(DeviceAction::with_hazard(decrease_config, decrease_temperature, Hazard::ElectricEnergyConsumption))?
+        .add_action(DeviceAction::with_hazard(Route::put("/target").input(Input::characters_sequence("temperature", "4")), set_temperature, Hazard::SpoiledFood))?
+        .build()?;
+
+}
@@ -0,0 +1,12 @@
+fn firmware() {
+
+    let device = NewDevice::new(
+        DeviceAction::with_hazard(Route::put("/first").description("First action."), first_action, Hazard::FireHazard),
+        DeviceAction::with_hazards(Route::put("/second").description("Second action."), second_action, &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard]),
+        DeviceAction::no_hazards(Route::put("/third").description("Third action."), third_action)
+    )?
+    .add_action(DeviceAction::no_hazards(optional_action_config, optional_action))?
+    .state(device_state)
+    .build();
+
+}
@@ -0,0 +1,20 @@
+async fn light_on() -> Result<DevicePayload, DeviceError> {
+    Ok(DevicePayload::empty())
+}
+
+fn firmware() {
+
+    let light = Light::new(
+        DeviceAction::with_hazard(Route::put("/on"), light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(Route::put("/off"), turn_light_off),
+    )?
+    .add_action(DeviceAction::with_hazard(Route::post("/enable"), light_on, Hazard::FireHazard))?
+    .build();
+
+    let fridge = Fridge::new()
+        .increase_temperature(DeviceAction::with_hazards(increase_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?
+        .decrease_temperature(DeviceAction::with_hazard(decrease_config, decrease_temperature, Hazard::ElectricEnergyConsumption))?
+        .increase_temperature(DeviceAction::with_hazards(increase_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?
+        .build()?;
+
+}
@@ -0,0 +1,22 @@
+use ascot_axum::devices::fridge::Fridge;
+
+fn firmware() {
+
+    let fridge = Fridge::new()
+        .increase_temperature(DeviceAction::with_hazards(increase_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?;
+
+    // The mandatory action is defined only with the `eco` feature.
+    #[cfg(feature = "eco")]
+    let fridge = fridge.decrease_temperature(DeviceAction::with_hazard(decrease_config, decrease_temperature, Hazard::ElectricEnergyConsumption))?;
+
+    #[cfg(all(feature = "debug", not(test)))]
+    let fridge = fridge.add_action(DeviceAction::with_hazard(debug_config, debug_action, Hazard::FireHazard))?;
+
+    let fridge = fridge.build()?;
+
+    AscotServer::new(fridge)
+        .run_service(ServiceBuilder::new("mdns-sd"))?
+        .run()
+        .await?;
+
+}
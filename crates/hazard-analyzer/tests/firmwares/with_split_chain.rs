@@ -0,0 +1,17 @@
+fn firmware() {
+
+    let mut device = NewDevice::new(
+        DeviceAction::with_hazard(first_action_config, first_action, Hazard::FireHazard),
+        DeviceAction::with_hazards(second_action_config, second_action, &[Hazard::ElectricEnergyConsumption, Hazard::FireHazard]),
+        DeviceAction::no_hazards(third_action_config, third_action)
+    )?;
+
+    device = device.add_action(DeviceAction::no_hazards(optional_action_config, optional_action))?;
+
+    if save_energy {
+        device = device.add_action(DeviceAction::with_hazard(save_energy_config, save_energy_action, Hazard::ElectricEnergyConsumption))?;
+    }
+
+    let device = device.state(device_state).build();
+
+}
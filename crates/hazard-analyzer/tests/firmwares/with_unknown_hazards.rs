@@ -0,0 +1,11 @@
+fn firmware() {
+
+    let device = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazzard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .add_action(DeviceAction::with_hazards(toggle_config, toggle, &[Hazard::ElectricEnergyConsumption, Hazard::Teleportation]))?
+    .add_action(DeviceAction::with_hazard(save_energy_config, save_energy, Hazard::SpoiledFood))?
+    .build();
+
+}
@@ -0,0 +1,15 @@
+fn firmware() {
+
+    let light_on_config = Route::get("/on").description("Turn light on.");
+    let light_off_config = Route::put("/off").description("Turn light off.");
+
+    let device = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .add_action(DeviceAction::no_hazards(Route::get("/state"), get_state))?
+    .add_action(DeviceAction::with_hazard(Route::delete("/energy"), save_energy, Hazard::ElectricEnergyConsumption))?
+    .state(device_state)
+    .build();
+
+}
@@ -0,0 +1,11 @@
+use ascot_axum::devices::light::Light;
+
+fn main() {
+
+    let light = Light::new(
+        DeviceAction::no_hazards(light_on_config, turn_light_on),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+}
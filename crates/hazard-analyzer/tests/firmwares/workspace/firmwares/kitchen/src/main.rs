@@ -0,0 +1,9 @@
+use ascot_axum::devices::fridge::Fridge;
+
+fn main() {
+
+    let fridge = Fridge::new()
+        .increase_temperature(DeviceAction::with_hazards(increase_config, increase_temperature, &[Hazard::ElectricEnergyConsumption, Hazard::SpoiledFood]))?
+        .build()?;
+
+}
@@ -0,0 +1,11 @@
+use ascot_axum::devices::light::Light;
+
+fn main() {
+
+    let light = Light::new(
+        DeviceAction::with_hazard(light_on_config, turn_light_on, Hazard::FireHazard),
+        DeviceAction::no_hazards(light_off_config, turn_light_off),
+    )?
+    .build();
+
+}
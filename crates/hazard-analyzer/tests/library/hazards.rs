@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+/// All possible hazards for a device action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Hazard {
+    /// The execution may release toxic gases.
+    AirPoisoning,
+    /// The execution may cause oxygen deficiency by gaseous substances.
+    Asphyxia,
+    /// The execution authorises the app to display a video with audio coming
+    /// from the device.
+    AudioVideoDisplay,
+    /// The execution authorises the app to record and save a video with audio
+    /// on persistent storage.
+    AudioVideoRecordAndStore,
+    /// The execution enables a device that consumes electricity.
+    ElectricEnergyConsumption,
+    /// The execution may cause an explosion.
+    Explosion,
+    /// The execution may cause fire.
+    FireHazard,
+    /// The execution enables a device that consumes gas.
+    GasConsumption,
+    /// The execution authorises the app to get and save information about
+    /// the app's energy impact on the device the app runs on.
+    LogEnergyConsumption,
+    /// The execution authorises the app to get and save information about
+    /// the app's duration of use.
+    LogUsageTime,
+    /// The execution authorises the app to use payment information and make
+    /// a periodic payment.
+    PaySubscriptionFee,
+    /// The execution may cause an interruption in the supply of electricity.
+    PowerOutage,
+    /// The execution may lead to exposure to high voltages.
+    PowerSurge,
+    /// The execution authorises the app to get and save user inputs.
+    RecordIssuedCommands,
+    /// The execution authorises the app to get and save information about
+    /// the user's preferences.
+    RecordUserPreferences,
+    /// The execution authorises the app to use payment information and make
+    /// a payment transaction.
+    SpendMoney,
+    /// The execution may lead to rotten food.
+    SpoiledFood,
+    /// The execution authorises the app to read the display output and take
+    /// screenshots of it.
+    TakeDeviceScreenshots,
+    /// The execution authorises the app to use a camera and take photos.
+    TakePictures,
+    /// The execution disables a protection mechanism and unauthorised
+    /// individuals may physically access to the environment.
+    UnauthorisedPhysicalAccess,
+    /// The execution enables a device that consumes water.
+    WaterConsumption,
+    /// The execution allows water usage which may lead to flood.
+    WaterFlooding,
+}
+
+/// Hazard category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Category {
+    /// Financial hazards.
+    Financial,
+    /// Privacy hazards.
+    Privacy,
+    /// Safety hazards.
+    Safety,
+}
+
+impl Hazard {
+    /// Returns the hazard identifier.
+    pub const fn id(&self) -> u16 {
+        match self {
+            Self::AirPoisoning => 0,
+            Self::Asphyxia => 1,
+            Self::AudioVideoDisplay => 2,
+            Self::AudioVideoRecordAndStore => 3,
+            Self::ElectricEnergyConsumption => 4,
+            Self::Explosion => 5,
+            Self::FireHazard => 6,
+            Self::GasConsumption => 7,
+            Self::LogEnergyConsumption => 8,
+            Self::LogUsageTime => 9,
+            Self::PaySubscriptionFee => 10,
+            Self::PowerOutage => 11,
+            Self::PowerSurge => 12,
+            Self::RecordIssuedCommands => 13,
+            Self::RecordUserPreferences => 14,
+            Self::SpendMoney => 15,
+            Self::SpoiledFood => 16,
+            Self::TakeDeviceScreenshots => 17,
+            Self::TakePictures => 18,
+            Self::UnauthorisedPhysicalAccess => 19,
+            Self::WaterConsumption => 20,
+            Self::WaterFlooding => 21,
+        }
+    }
+
+    /// Returns the hazard category.
+    pub const fn category(&self) -> Category {
+        match self {
+            Self::AirPoisoning
+            | Self::Asphyxia
+            | Self::Explosion
+            | Self::FireHazard
+            | Self::PowerOutage
+            | Self::PowerSurge
+            | Self::SpoiledFood
+            | Self::UnauthorisedPhysicalAccess
+            | Self::WaterFlooding => Category::Safety,
+            Self::ElectricEnergyConsumption
+            | Self::GasConsumption
+            | Self::PaySubscriptionFee
+            | Self::SpendMoney
+            | Self::WaterConsumption => Category::Financial,
+            Self::AudioVideoDisplay
+            | Self::AudioVideoRecordAndStore
+            | Self::LogEnergyConsumption
+            | Self::LogUsageTime
+            | Self::RecordIssuedCommands
+            | Self::RecordUserPreferences
+            | Self::TakeDeviceScreenshots
+            | Self::TakePictures => Category::Privacy,
+        }
+    }
+}
@@ -1,9 +1,14 @@
-use hazard_analyzer::hazard_analyzer;
+use hazard_analyzer::{hazard_analyzer, lint_devices, AnalysisOptions};
 use insta::sorted_redaction;
 use serde_json::Value;
-use std::{env::temp_dir, fs, path::Path};
+use std::{
+    env::temp_dir,
+    fs,
+    path::{Path, PathBuf},
+};
 
 const SNAPSHOTS_PATH: &str = "./snapshots/firmwares/";
+const LINTS_SNAPSHOTS_PATH: &str = "./snapshots/lints/";
 const TEST_DEVICES_PATH: &str = "./tests/devices/";
 
 #[test]
@@ -24,6 +29,37 @@ fn ascot_fridge() {
     )
 }
 
+#[test]
+fn crate_firmware() {
+    compare(
+        "crate_firmware",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/crate_firmware/Cargo.toml"),
+    )
+}
+
+#[test]
+fn gated_crate_firmware() {
+    compare_with_options(
+        "gated_crate_firmware",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/gated_crate_firmware/Cargo.toml"),
+        &AnalysisOptions {
+            all_feature_combinations: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn workspace() {
+    compare_workspace(
+        "workspace",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/workspace/Cargo.toml"),
+    )
+}
+
 #[test]
 fn firmware_with_new_device() {
     compare(
@@ -42,6 +78,161 @@ fn firmware_with_methods_device() {
     )
 }
 
+#[test]
+fn firmware_with_aliased_devices() {
+    compare(
+        "with_aliased_devices",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_aliased_devices.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_bound_actions() {
+    compare(
+        "with_bound_actions",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_bound_actions.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_comments() {
+    compare(
+        "with_comments",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_comments.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_device_status() {
+    compare(
+        "with_device_status",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_device_status.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_duplicate_actions() {
+    compare(
+        "with_duplicate_actions",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_duplicate_actions.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_repeated_actions() {
+    compare(
+        "with_repeated_actions",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_repeated_actions.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_feature_combinations() {
+    compare_with_options(
+        "with_feature_combinations",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_feature_gates.rs"),
+        &AnalysisOptions {
+            all_feature_combinations: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn firmware_with_feature_gates() {
+    compare_with_options(
+        "with_feature_gates",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_feature_gates.rs"),
+        &AnalysisOptions {
+            features: Some(vec!["eco".to_string()]),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn firmware_with_handler_bodies() {
+    compare_with_options(
+        "with_handler_bodies",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_handler_bodies.rs"),
+        &AnalysisOptions {
+            handler_body_depth: Some(1),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn firmware_with_handlers() {
+    compare(
+        "with_handlers",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_handlers.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_hazard_consts() {
+    compare(
+        "with_hazard_consts",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_hazard_consts"),
+    )
+}
+
+#[test]
+fn firmware_with_helper_actions() {
+    compare(
+        "with_helper_actions",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_helper_actions"),
+    )
+}
+
+#[test]
+fn firmware_with_helper_device() {
+    compare(
+        "with_helper_device",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_helper_device"),
+    )
+}
+
+#[test]
+fn firmware_with_http_methods_policy() {
+    compare_with_options(
+        "with_http_methods_policy",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_unsafe_methods.rs"),
+        &AnalysisOptions {
+            policy_path: Some(PathBuf::from("./tests/policies/http_methods.toml")),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn firmware_with_input_ranges() {
+    compare_with_options(
+        "with_input_ranges",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_input_ranges.rs"),
+        &AnalysisOptions {
+            policy_path: Some(PathBuf::from("./tests/policies/input_ranges.toml")),
+            ..Default::default()
+        },
+    )
+}
+
 #[test]
 fn firmware_with_multiple_devices() {
     compare(
@@ -51,6 +242,90 @@ fn firmware_with_multiple_devices() {
     )
 }
 
+#[test]
+fn firmware_with_nested_calls() {
+    compare(
+        "with_nested_calls",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_nested_calls.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_served_feature_combinations() {
+    compare_with_options(
+        "with_served_feature_combinations",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_served_feature_gates.rs"),
+        &AnalysisOptions {
+            all_feature_combinations: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn firmware_with_split_chain() {
+    compare(
+        "with_split_chain",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_split_chain.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_unsafe_methods() {
+    compare(
+        "with_unsafe_methods",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/with_unsafe_methods.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_hazard_checks() {
+    compare(
+        "with_hazard_checks",
+        Path::new("./tests/devices_with_hazard_checks/"),
+        Path::new("./tests/firmwares/with_hazard_checks.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_device_modules() {
+    compare(
+        "with_device_modules",
+        Path::new("./tests/devices_with_modules/"),
+        Path::new("./tests/firmwares/ascot_light.rs"),
+    )
+}
+
+#[test]
+fn firmware_with_hazard_catalog() {
+    compare_with_options(
+        "with_hazard_catalog",
+        Path::new(TEST_DEVICES_PATH),
+        Path::new("./tests/firmwares/ascot_light.rs"),
+        &AnalysisOptions {
+            hazards_path: Some(PathBuf::from("./tests/library/hazards.rs")),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn firmware_with_unknown_hazards() {
+    compare_with_options(
+        "with_unknown_hazards",
+        Path::new("./tests/devices_with_unknown_hazards/"),
+        Path::new("./tests/firmwares/with_unknown_hazards.rs"),
+        &AnalysisOptions {
+            hazards_path: Some(PathBuf::from("./tests/library/hazards.rs")),
+            ..Default::default()
+        },
+    )
+}
+
 #[test]
 fn firmware_without_mandatory_actions() {
     compare(
@@ -78,10 +353,97 @@ fn firmware_with_not_allowed_hazards() {
     )
 }
 
+#[test]
+fn lint_ascot_devices() {
+    compare_lints("lint_devices", Path::new(TEST_DEVICES_PATH))
+}
+
+#[test]
+fn lint_composed_devices() {
+    compare_lints(
+        "lint_composed_devices",
+        Path::new("./tests/devices_with_composed_hazards/"),
+    )
+}
+
+#[test]
+fn lint_device_structs() {
+    compare_lints(
+        "lint_device_structs",
+        Path::new("./tests/devices_with_structs/"),
+    )
+}
+
+#[test]
+fn lint_inconsistent_devices() {
+    compare_lints(
+        "lint_inconsistent_devices",
+        Path::new("./tests/devices_with_inconsistencies/"),
+    )
+}
+
 fn compare(snapshot_name: &str, devices_path: &Path, firmware_path: &Path) {
+    compare_with_options(
+        snapshot_name,
+        devices_path,
+        firmware_path,
+        &AnalysisOptions::default(),
+    )
+}
+
+fn compare_with_options(
+    snapshot_name: &str,
+    devices_path: &Path,
+    firmware_path: &Path,
+    options: &AnalysisOptions,
+) {
+    let output_path = temp_dir().join(Path::new(snapshot_name));
+
+    hazard_analyzer(
+        Some(devices_path),
+        firmware_path,
+        &output_path,
+        true,
+        options,
+    )
+    .unwrap();
+
+    let manifest_str = fs::read_to_string(&output_path).unwrap();
+    let manifest: Value = serde_json::from_str(&manifest_str).unwrap();
+
+    insta::with_settings!({
+        snapshot_path => Path::new(SNAPSHOTS_PATH),
+        prepend_module_to_snapshot => false,
+    },{
+        insta::assert_yaml_snapshot!(snapshot_name, manifest, {
+            ".files" => sorted_redaction(),
+            ".unreachableFiles" => sorted_redaction(),
+            ".files[].devices" => sorted_redaction(),
+            ".files[].devices.*.mandatoryActions" => sorted_redaction(),
+            ".files[].devices.*.mandatoryActions.*.hazards" => sorted_redaction(),
+            ".files[].devices.*.mandatoryActions.*.mandatoryHazards" => sorted_redaction(),
+            ".files[].devices.*.mandatoryActions.*.missingHazards" => sorted_redaction(),
+            ".files[].devices.*.mandatoryActions.*.notAllowdHazards" => sorted_redaction(),
+            ".files[].devices.*.missingMandatoryActions" => sorted_redaction(),
+            ".files[].devices.*.optionalActions" => sorted_redaction(),
+            ".files[].devices.*.optionalActions.*.hazards" => sorted_redaction(),
+            ".files[].devices.*.optionalActions.*.notAllowedHazards" => sorted_redaction(),
+            ".files[].devices.*.allowedHazards" => sorted_redaction(),
+        });
+    });
+}
+
+fn compare_workspace(snapshot_name: &str, devices_path: &Path, workspace_path: &Path) {
     let output_path = temp_dir().join(Path::new(snapshot_name));
 
-    hazard_analyzer(Some(devices_path), firmware_path, &output_path, true).unwrap();
+    hazard_analyzer(
+        Some(devices_path),
+        workspace_path,
+        &output_path,
+        true,
+        &AnalysisOptions::default(),
+    )
+    .unwrap();
 
     let manifest_str = fs::read_to_string(&output_path).unwrap();
     let manifest: Value = serde_json::from_str(&manifest_str).unwrap();
@@ -91,18 +453,35 @@ fn compare(snapshot_name: &str, devices_path: &Path, firmware_path: &Path) {
         prepend_module_to_snapshot => false,
     },{
         insta::assert_yaml_snapshot!(snapshot_name, manifest, {
-            "." => sorted_redaction(),
-            "[].devices" => sorted_redaction(),
-            "[].devices.*.mandatoryActions" => sorted_redaction(),
-            "[].devices.*.mandatoryActions.*.hazards" => sorted_redaction(),
-            "[].devices.*.mandatoryActions.*.mandatoryHazards" => sorted_redaction(),
-            "[].devices.*.mandatoryActions.*.missingHazards" => sorted_redaction(),
-            "[].devices.*.mandatoryActions.*.notAllowdHazards" => sorted_redaction(),
-            "[].devices.*.missingMandatoryActions" => sorted_redaction(),
-            "[].devices.*.optionalActions" => sorted_redaction(),
-            "[].devices.*.optionalActions.*.hazards" => sorted_redaction(),
-            "[].devices.*.optionalActions.*.notAllowedHazards" => sorted_redaction(),
-            "[].devices.*.allowedHazards" => sorted_redaction(),
+            ".packages[].files" => sorted_redaction(),
+            ".packages[].unreachableFiles" => sorted_redaction(),
+            ".packages[].files[].devices" => sorted_redaction(),
+            ".packages[].files[].devices.*.mandatoryActions" => sorted_redaction(),
+            ".packages[].files[].devices.*.mandatoryActions.*.hazards" => sorted_redaction(),
+            ".packages[].files[].devices.*.mandatoryActions.*.mandatoryHazards" => sorted_redaction(),
+            ".packages[].files[].devices.*.mandatoryActions.*.missingHazards" => sorted_redaction(),
+            ".packages[].files[].devices.*.mandatoryActions.*.notAllowedHazards" => sorted_redaction(),
+            ".packages[].files[].devices.*.missingMandatoryActions" => sorted_redaction(),
+            ".packages[].files[].devices.*.optionalActions" => sorted_redaction(),
+            ".packages[].files[].devices.*.optionalActions.*.hazards" => sorted_redaction(),
+            ".packages[].files[].devices.*.optionalActions.*.notAllowedHazards" => sorted_redaction(),
+            ".packages[].files[].devices.*.allowedHazards" => sorted_redaction(),
         });
     });
 }
+
+fn compare_lints(snapshot_name: &str, devices_path: &Path) {
+    let output_path = temp_dir().join(Path::new(snapshot_name));
+
+    lint_devices(Some(devices_path), &output_path, true).unwrap();
+
+    let manifest_str = fs::read_to_string(&output_path).unwrap();
+    let manifest: Value = serde_json::from_str(&manifest_str).unwrap();
+
+    insta::with_settings!({
+        snapshot_path => Path::new(LINTS_SNAPSHOTS_PATH),
+        prepend_module_to_snapshot => false,
+    },{
+        insta::assert_yaml_snapshot!(snapshot_name, manifest);
+    });
+}